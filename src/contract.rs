@@ -1,28 +1,112 @@
+use std::cmp::Reverse;
+
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Addr, BankMsg, Binary, Coin, Deps, DepsMut, Env, MessageInfo, Response, StdError,
-    StdResult, Timestamp,
+    from_json, to_json_binary, Addr, BankMsg, Binary, Coin, Decimal, Deps, DepsMut,
+    DistributionMsg, Empty, Env, MessageInfo, Order, QuerierWrapper, Reply, Response, StakingMsg,
+    StdError, StdResult, Storage, SubMsg, Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
+use cw4::{Cw4QueryMsg, MemberResponse};
+use sha2::{Digest, Sha256};
 
+use crate::cw721::{BadgeMetadata, Cw721ExecuteMsg, Cw721ReceiveMsg};
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
-use crate::state::{Bounty, BountyStatus, BOUNTIES, NEXT_BOUNTY_ID};
+use crate::hooks::{prepare_hooks, BountyHookMsg, HOOK_REPLY_ID};
+use crate::ibc::{handle_transfer_reply, ibc_payout, IBC_TRANSFER_REPLY_ID};
+use crate::merkle::{leaf_hash, verify_proof};
+use crate::msg::{
+    AbandonMsg, ApplicationsResponse, ApplyMsg, AssignHunterMsg, AttestCompletionMsg,
+    ClaimAndSwapMsg, ClaimMerkleMsg, ClaimMsg, ClaimPayoutMsg, ClaimVestedMsg, ClaimableResponse,
+    CommitSubmissionMsg, CreateBountyMsg, CreateProgramMsg, EntriesResponse, ExecuteAttestationMsg,
+    ExecuteMsg, ExpireBountyMsg, FinalizeBountyMsg, FreezePayoutMsg, FundBountyMsg, FundProgramMsg,
+    FundRemoteBountyMsg, HarvestYieldMsg, HookMsg, HooksResponse, InstantiateMsg,
+    PostMerkleRootMsg, QueryMsg, RateReportMsg, ReceiveNftMsg, ReleaseAssignmentMsg,
+    ReportsResponse, ResolveFrozenPayoutMsg, RevealSubmissionMsg, SetPayoutDestinationMsg,
+    SettleContestMsg, SignedFinalizeMsg, StakeBountyMsg, StakingConfigMsg, SubmitEntryMsg,
+    SubmitReportMsg, SubmitWorkMsg, SweepMerkleMsg, UnstakeBountyMsg, UpdateConfigMsg,
+    VestingResponse, VetoAttestationMsg, VoteEntryMsg, VotersMsg, VotesResponse,
+    WithdrawApplicationMsg, WithdrawExcessMsg, WithdrawProgramMsg, YieldRecipientMsg,
+};
+use crate::oracle::{OracleQueryMsg, PriceResponse};
+use crate::router::RouterExecuteMsg;
+use crate::signature::{verify_approval, ApprovalPayload};
+use crate::staking::{StakingQueryMsg, VotingPowerAtHeightResponse};
+use crate::state::{
+    check_expired, credit_claimable, ensure_bounty_open, Assignment, Attestation, Bounty,
+    BountyStatus, Config, Contest, Entry, IssuerGroup, MerkleDistribution, NftReward, PendingSwap,
+    Program, Recurrence, RemoteController, Report, ReportStatus, SecurityPool, Stake,
+    StakingConfig, VestingSchedule, Voters, YieldRecipient, APPLICATIONS, BOUNTIES, CLAIMABLE,
+    COMMITMENTS, CONFIG, ENTRANTS, ENTRIES, ENTRY_VOTES, HOOKS, MERKLE_CLAIMED, NEXT_BOUNTY_ID,
+    NEXT_PROGRAM_ID, PENDING_REMOTE_BOUNTIES, PROGRAMS, REMOTE_BOUNTIES, REPORTS, TOTAL_STAKED,
+    VESTING, VOTES, YIELD_INDEX,
+};
+use crate::validation::{
+    validate_create_bounty, validate_funds, validate_ibc_destination,
+    DEFAULT_MAX_DESCRIPTION_LENGTH, DEFAULT_MAX_TITLE_LENGTH, DEFAULT_VETO_PERIOD,
+    MAX_CONTEST_ENTRIES,
+};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:{{project-name}}";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Reply ID of router swaps, distinct from `IBC_TRANSFER_REPLY_ID`
+pub const SWAP_REPLY_ID: u64 = 2;
+
+/// Reply id of NFT prize transfers, which must not block the bounty from settling
+pub const NFT_TRANSFER_REPLY_ID: u64 = 4;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
-    _msg: InstantiateMsg,
+    msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
-    NEXT_BOUNTY_ID.save(deps.storage, &1u64)?; // Initialize ID counter
-    Ok(Response::new().add_attribute("method", "instantiate").add_attribute("bounty_owner", info.sender))
+    let config = Config {
+        admin: msg
+            .admin
+            .map(|admin| deps.api.addr_validate(&admin))
+            .transpose()?
+            .unwrap_or_else(|| info.sender.clone()),
+        max_title_length: msg.max_title_length.unwrap_or(DEFAULT_MAX_TITLE_LENGTH),
+        max_description_length: msg
+            .max_description_length
+            .unwrap_or(DEFAULT_MAX_DESCRIPTION_LENGTH),
+        badge_contract: msg
+            .badge_contract
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?,
+        staking: msg
+            .staking
+            .map(|staking| validate_staking_config(deps.as_ref(), staking))
+            .transpose()?,
+        price_oracle: msg
+            .price_oracle
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?,
+        swap_router: msg
+            .swap_router
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?,
+        nft_contracts: msg
+            .nft_contracts
+            .unwrap_or_default()
+            .iter()
+            .map(|addr| deps.api.addr_validate(addr))
+            .collect::<StdResult<_>>()?,
+    };
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    CONFIG.save(deps.storage, &config)?;
+    NEXT_BOUNTY_ID.save(deps.storage, &msg.start_bounty_id)?;
+    NEXT_PROGRAM_ID.save(deps.storage, &1u64)?;
+    TOTAL_STAKED.save(deps.storage, &Uint128::zero())?;
+    YIELD_INDEX.save(deps.storage, &Decimal::zero())?;
+    Ok(Response::new()
+        .add_attribute("method", "instantiate")
+        .add_attribute("bounty_owner", info.sender))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -33,9 +117,49 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::CreateBounty(msg) => execute_create_bounty(deps, env, info, msg),
+        ExecuteMsg::CreateBounty(msg) => execute_create_bounty(deps, env, info, *msg),
+        ExecuteMsg::ReceiveNft(msg) => execute_receive_nft(deps, env, info, msg),
+        ExecuteMsg::FundRemoteBounty(msg) => execute_fund_remote_bounty(deps, env, info, msg),
+        ExecuteMsg::Apply(msg) => execute_apply(deps, env, info, msg),
+        ExecuteMsg::AssignHunter(msg) => execute_assign_hunter(deps, env, info, msg),
+        ExecuteMsg::Abandon(msg) => execute_abandon(deps, env, info, msg),
+        ExecuteMsg::WithdrawApplication(msg) => execute_withdraw_application(deps, info, msg),
+        ExecuteMsg::SubmitWork(msg) => execute_submit_work(deps, env, info, msg),
+        ExecuteMsg::ReleaseAssignment(msg) => execute_release_assignment(deps, env, msg),
         ExecuteMsg::FinalizeBounty(msg) => execute_finalize_bounty(deps, env, info, msg),
+        ExecuteMsg::SetPayoutDestination(msg) => execute_set_payout_destination(deps, info, msg),
+        ExecuteMsg::FinalizeWithSignature(msg) => execute_finalize_with_signature(deps, env, msg),
+        ExecuteMsg::AttestCompletion(msg) => execute_attest_completion(deps, env, info, msg),
+        ExecuteMsg::VetoAttestation(msg) => execute_veto_attestation(deps, env, info, msg),
+        ExecuteMsg::ExecuteAttestation(msg) => execute_execute_attestation(deps, env, msg),
+        ExecuteMsg::ClaimPayout(msg) => execute_claim_payout(deps, env, info, msg),
+        ExecuteMsg::FreezePayout(msg) => execute_freeze_payout(deps, env, info, msg),
+        ExecuteMsg::ResolveFrozenPayout(msg) => execute_resolve_frozen_payout(deps, env, info, msg),
+        ExecuteMsg::ClaimVested(msg) => execute_claim_vested(deps, env, info, msg),
+        ExecuteMsg::Claim(msg) => execute_claim(deps, env, info, msg),
+        ExecuteMsg::ClaimAndSwap(msg) => execute_claim_and_swap(deps, env, info, msg),
+        ExecuteMsg::SubmitEntry(msg) => execute_submit_entry(deps, env, info, msg),
+        ExecuteMsg::CommitSubmission(msg) => execute_commit_submission(deps, env, info, msg),
+        ExecuteMsg::RevealSubmission(msg) => execute_reveal_submission(deps, env, info, msg),
+        ExecuteMsg::VoteEntry(msg) => execute_vote_entry(deps, env, info, msg),
+        ExecuteMsg::SettleContest(msg) => execute_settle_contest(deps, env, msg),
+        ExecuteMsg::SubmitReport(msg) => execute_submit_report(deps, env, info, msg),
+        ExecuteMsg::RateReport(msg) => execute_rate_report(deps, info, msg),
+        ExecuteMsg::PostMerkleRoot(msg) => execute_post_merkle_root(deps, env, info, msg),
+        ExecuteMsg::ClaimMerkle(msg) => execute_claim_merkle(deps, env, info, msg),
+        ExecuteMsg::SweepMerkle(msg) => execute_sweep_merkle(deps, env, msg),
         ExecuteMsg::ExpireBounty(msg) => execute_expire_bounty(deps, env, info, msg),
+        ExecuteMsg::UpdateConfig(msg) => execute_update_config(deps, info, msg),
+        ExecuteMsg::FundBounty(msg) => execute_fund_bounty(deps, env, info, msg),
+        ExecuteMsg::AddHook(msg) => execute_add_hook(deps, info, msg),
+        ExecuteMsg::RemoveHook(msg) => execute_remove_hook(deps, info, msg),
+        ExecuteMsg::WithdrawExcess(msg) => execute_withdraw_excess(deps, env, info, msg),
+        ExecuteMsg::StakeBounty(msg) => execute_stake_bounty(deps, env, info, msg),
+        ExecuteMsg::UnstakeBounty(msg) => execute_unstake_bounty(deps, env, info, msg),
+        ExecuteMsg::HarvestYield(msg) => execute_harvest_yield(deps, env, msg),
+        ExecuteMsg::CreateProgram(msg) => execute_create_program(deps, info, msg),
+        ExecuteMsg::FundProgram(msg) => execute_fund_program(deps, info, msg),
+        ExecuteMsg::WithdrawProgram(msg) => execute_withdraw_program(deps, info, msg),
     }
 }
 
@@ -46,345 +170,4869 @@ pub fn execute_create_bounty(
     info: MessageInfo,
     msg: CreateBountyMsg,
 ) -> Result<Response, ContractError> {
-    let id = NEXT_BOUNTY_ID.load(deps.storage)?;
+    create_bounty(deps, &env, info.sender, &info.funds, vec![], msg, None)
+}
 
-    if info.funds.is_empty() || info.funds[0].denom != msg.token_denom {
-        return Err(ContractError::InvalidFunds {});
+/// Handle a cw721 token sent as the prize of a new or existing bounty
+pub fn execute_receive_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    // The sender is reported by the cw721 contract, so only trusted contracts can vouch for it
+    let config = CONFIG.load(deps.storage)?;
+    if !config.nft_contracts.contains(&info.sender) {
+        return Err(ContractError::NftContractNotAllowed {});
     }
-    if info.funds[0].amount < msg.quantity {
-        return Err(ContractError::InsufficientFunds {});
+    let owner = deps.api.addr_validate(&msg.sender)?;
+    let reward = NftReward {
+        contract: info.sender,
+        token_id: msg.token_id,
+    };
+
+    match from_json(&msg.msg)? {
+        ReceiveNftMsg::CreateBounty(bounty) => {
+            create_bounty(deps, &env, owner, &[], vec![reward], *bounty, None)
+        }
+        ReceiveNftMsg::AddReward { bounty_id } => {
+            let mut bounty = BOUNTIES.load(deps.storage, bounty_id)?;
+
+            ensure_issuer(deps.as_ref(), &bounty, &owner)?;
+            ensure_bounty_open(&bounty)?;
+            if check_expired(&bounty, &env) {
+                return Err(ContractError::BountyExpired {});
+            }
+
+            let res = Response::new()
+                .add_attribute("action", "add_nft_reward")
+                .add_attribute("bounty_id", bounty_id.to_string())
+                .add_attribute("nft_contract", reward.contract.to_string())
+                .add_attribute("token_id", reward.token_id.clone());
+
+            bounty.nft_rewards.push(reward);
+            BOUNTIES.save(deps.storage, bounty_id, &bounty)?;
+
+            Ok(res)
+        }
     }
+}
+
+/// Create a bounty funded by `issuer` with `funds` and any escrowed NFTs, optionally controlled
+/// over IBC
+pub(crate) fn create_bounty(
+    deps: DepsMut,
+    env: &Env,
+    issuer: Addr,
+    funds: &[Coin],
+    nft_rewards: Vec<NftReward>,
+    msg: CreateBountyMsg,
+    controller: Option<RemoteController>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    validate_create_bounty(&config, env, &msg, &nft_rewards)?;
+    let cycles = msg.recurrence.as_ref().map_or(1, |r| r.max_cycles);
+    let total = msg
+        .quantity
+        .checked_mul(Uint128::from(cycles))
+        .map_err(|_| ContractError::InvalidRecurrence {})?;
+    let excess = match msg.program_id {
+        Some(program_id) => {
+            charge_program(deps.storage, program_id, &issuer, funds, &msg)?;
+            Uint128::zero()
+        }
+        None => validate_funds(funds, &msg.token_denom, total)?,
+    };
+    // Program bounties are funded cycle by cycle, others set aside the later cycles upfront
+    let reserve = match msg.program_id {
+        Some(_) => Uint128::zero(),
+        None => total - msg.quantity,
+    };
+
+    let id = NEXT_BOUNTY_ID.load(deps.storage)?;
 
+    let recipient = msg
+        .recipient
+        .map(|r| deps.api.addr_validate(&r))
+        .transpose()?;
     let bounty = Bounty {
         title: msg.title,
         description: msg.description,
         status: BountyStatus::Open,
-        issuer: info.sender.clone(),
-        recipient: msg.recipient.map(|r| deps.api.addr_validate(&r)).transpose()?,
+        issuer: issuer.clone(),
+        recipient: recipient.clone(),
         end_height: msg.end_height,
         end_time: msg.end_time,
         token_denom: msg.token_denom,
         quantity: msg.quantity,
-        balance: info.funds[0].amount,
+        balance: msg.quantity,
+        approver_key: msg.approver_key,
+        nonce: 0,
+        oracle: msg.oracle.map(|o| deps.api.addr_validate(&o)).transpose()?,
+        veto_period: msg.veto_period.unwrap_or(DEFAULT_VETO_PERIOD),
+        attestation: None,
+        release_delay: msg.release_delay,
+        arbiter: msg
+            .arbiter
+            .map(|a| deps.api.addr_validate(&a))
+            .transpose()?,
+        vesting: msg.vesting,
+        pull_payments: msg.pull_payments,
+        payout_destination: None,
+        controller,
+        hooks: msg
+            .hooks
+            .iter()
+            .map(|h| deps.api.addr_validate(h))
+            .collect::<StdResult<_>>()?,
+        tags: msg.tags,
+        nft_rewards,
+        issuer_group: msg
+            .issuer_group
+            .map(|group| -> StdResult<_> {
+                Ok(IssuerGroup {
+                    contract: deps.api.addr_validate(&group.contract)?,
+                    min_weight: group.min_weight.unwrap_or(1),
+                })
+            })
+            .transpose()?,
+        reviewers: msg
+            .reviewers
+            .iter()
+            .map(|r| deps.api.addr_validate(r))
+            .collect::<StdResult<_>>()?,
+        threshold: msg.threshold,
+        merkle: None,
+        program_id: msg.program_id,
+        security: msg.severity_tiers.map(|tiers| SecurityPool {
+            tiers,
+            report_count: 0,
+        }),
+        hunter_bond: msg.hunter_bond,
+        held_bond: Uint128::zero(),
+        // A recipient named upfront is assigned from creation
+        assignment: msg
+            .assignment_period
+            .as_ref()
+            .filter(|_| recipient.is_some())
+            .map(|period| period.start(env)),
+        assignment_period: msg.assignment_period,
+        stake: None,
+        usd_value: msg.usd_value,
+        recurrence: msg.recurrence.map(|recurrence| Recurrence {
+            max_cycles: recurrence.max_cycles,
+            cycle: 1,
+            reserve,
+            span_height: msg.end_height.map(|h| h - env.block.height),
+            span_seconds: msg.end_time.map(|t| t.seconds() - env.block.time.seconds()),
+            recipient: recipient.clone(),
+        }),
+        contest: msg
+            .contest
+            .map(|contest| -> StdResult<_> {
+                Ok(Contest {
+                    reveal_end_height: contest.reveal_end_height,
+                    reveal_end_time: contest.reveal_end_time,
+                    voting_end_height: contest.voting_end_height,
+                    voting_end_time: contest.voting_end_time,
+                    voters: match contest.voters {
+                        VotersMsg::Whitelist(addresses) => Voters::Whitelist(
+                            addresses
+                                .iter()
+                                .map(|a| deps.api.addr_validate(a))
+                                .collect::<StdResult<_>>()?,
+                        ),
+                        VotersMsg::Staking(contract) => {
+                            Voters::Staking(deps.api.addr_validate(&contract)?)
+                        }
+                    },
+                    prizes: contest.prizes,
+                    snapshot_height: env.block.height,
+                    entry_count: 0,
+                })
+            })
+            .transpose()?,
     };
 
     BOUNTIES.save(deps.storage, id, &bounty)?;
     NEXT_BOUNTY_ID.save(deps.storage, &(id + 1))?;
 
-    Ok(Response::new()
+    let hook = BountyHookMsg::Created {
+        bounty_id: id,
+        issuer: issuer.clone(),
+    };
+    let mut res = Response::new()
+        .add_submessages(prepare_hooks(deps.storage, &bounty, hook)?)
         .add_attribute("action", "create_bounty")
         .add_attribute("bounty_id", id.to_string())
-        .add_attribute("issuer", info.sender.to_string()))
+        .add_attribute("issuer", issuer.to_string());
+
+    // Anything sent above the promised quantity goes straight back to the issuer
+    if !excess.is_zero() {
+        res = res
+            .add_message(BankMsg::Send {
+                to_address: issuer.to_string(),
+                amount: vec![Coin {
+                    denom: bounty.token_denom,
+                    amount: excess,
+                }],
+            })
+            .add_attribute("refunded", excess.to_string());
+    }
+
+    Ok(res)
 }
 
-/// Finalize a bounty
-pub fn execute_finalize_bounty(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    msg: FinalizeBountyMsg,
-) -> Result<Response, ContractError> {
-    let mut bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
+/// Take the quantity of a new child bounty from its program's budget
+fn charge_program(
+    storage: &mut dyn Storage,
+    program_id: u64,
+    issuer: &Addr,
+    funds: &[Coin],
+    msg: &CreateBountyMsg,
+) -> Result<(), ContractError> {
+    let mut program = PROGRAMS.load(storage, program_id)?;
 
-    if info.sender != bounty.issuer {
+    if !program.is_manager(issuer) {
         return Err(ContractError::Unauthorized {});
     }
-
-    if check_expired(&bounty, &env) {
-        bounty.status = BountyStatus::Expired;
+    if !funds.is_empty() || msg.token_denom != program.token_denom {
+        return Err(ContractError::InvalidFunds {});
+    }
+    if msg.quantity > program.balance {
+        return Err(ContractError::InsufficientFunds {});
     }
 
-    if msg.success {
-        let recipient = bounty.recipient.ok_or(ContractError::RecipientNotSet {})?;
-        bounty.status = BountyStatus::Completed;
+    program.balance -= msg.quantity;
+    PROGRAMS.save(storage, program_id, &program)?;
+    Ok(())
+}
 
-        let payment = BankMsg::Send {
-            to_address: recipient.to_string(),
-            amount: vec![Coin {
-                denom: bounty.token_denom.clone(),
-                amount: bounty.balance,
-            }],
-        };
+/// Fund and open a bounty registered by a remote controller, typically via an ICS-20 memo hook.
+///
+/// The funder is only recorded as the issuer to receive refunds, the controller manages the bounty.
+pub fn execute_fund_remote_bounty(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: FundRemoteBountyMsg,
+) -> Result<Response, ContractError> {
+    let key = (msg.channel_id.as_str(), msg.remote_id);
+    let pending = PENDING_REMOTE_BOUNTIES
+        .may_load(deps.storage, key)?
+        .ok_or(ContractError::UnknownRemoteBounty {})?;
+    PENDING_REMOTE_BOUNTIES.remove(deps.storage, key);
 
-        BOUNTIES.save(deps.storage, msg.bounty_id, &bounty)?;
+    let id = NEXT_BOUNTY_ID.load(deps.storage)?;
+    REMOTE_BOUNTIES.save(deps.storage, key, &id)?;
 
-        Ok(Response::new()
-            .add_message(payment)
-            .add_attribute("action", "finalize_bounty")
-            .add_attribute("bounty_id", msg.bounty_id.to_string())
-            .add_attribute("status", "completed"))
-    } else {
-        bounty.status = BountyStatus::Expired;
+    let controller = RemoteController {
+        channel_id: msg.channel_id,
+        remote_id: msg.remote_id,
+        refund_to: pending.refund_to,
+    };
+    create_bounty(
+        deps,
+        &env,
+        info.sender,
+        &info.funds,
+        vec![],
+        pending.bounty,
+        Some(controller),
+    )
+}
 
-        let refund = BankMsg::Send {
-            to_address: bounty.issuer.to_string(),
-            amount: vec![Coin {
-                denom: bounty.token_denom.clone(),
-                amount: bounty.balance,
-            }],
-        };
+/// Apply to an unassigned bounty, posting its hunter bond
+pub fn execute_apply(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ApplyMsg,
+) -> Result<Response, ContractError> {
+    let bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
 
-        BOUNTIES.save(deps.storage, msg.bounty_id, &bounty)?;
+    ensure_bounty_open(&bounty)?;
+    ensure_single_payout(&bounty)?;
+    if check_expired(&bounty, &env) {
+        return Err(ContractError::BountyExpired {});
+    }
+    if bounty.recipient.is_some() {
+        return Err(ContractError::HunterAssigned {});
+    }
+    let key = (msg.bounty_id, &info.sender);
+    if APPLICATIONS.has(deps.storage, key) {
+        return Err(ContractError::AlreadyApplied {});
+    }
 
-        Ok(Response::new()
-            .add_message(refund)
-            .add_attribute("action", "finalize_bounty")
-            .add_attribute("bounty_id", msg.bounty_id.to_string())
-            .add_attribute("status", "expired"))
+    let bond = bounty.hunter_bond.unwrap_or_default();
+    if !validate_funds(&info.funds, &bounty.token_denom, bond)?.is_zero() {
+        return Err(ContractError::InvalidFunds {});
     }
+    APPLICATIONS.save(deps.storage, key, &bond)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "apply")
+        .add_attribute("bounty_id", msg.bounty_id.to_string())
+        .add_attribute("hunter", info.sender)
+        .add_attribute("bond", bond))
 }
 
-/// Expire a bounty
-pub fn execute_expire_bounty(
+/// Assign a bounty to an applicant, holding their bond until the bounty is decided
+pub fn execute_assign_hunter(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    msg: ExpireBountyMsg,
+    msg: AssignHunterMsg,
 ) -> Result<Response, ContractError> {
     let mut bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
 
-    if info.sender != bounty.issuer {
-        return Err(ContractError::Unauthorized {});
+    ensure_issuer(deps.as_ref(), &bounty, &info.sender)?;
+    ensure_bounty_open(&bounty)?;
+    if check_expired(&bounty, &env) {
+        return Err(ContractError::BountyExpired {});
     }
-
-    if !check_expired(&bounty, &env) {
-        return Err(ContractError::NotYetExpired {});
+    if bounty.recipient.is_some() {
+        return Err(ContractError::HunterAssigned {});
     }
 
-    bounty.status = BountyStatus::Expired;
-
-    let refund = BankMsg::Send {
-        to_address: bounty.issuer.to_string(),
-        amount: vec![Coin {
-            denom: bounty.token_denom.clone(),
-            amount: bounty.balance,
-        }],
-    };
+    let hunter = deps.api.addr_validate(&msg.hunter)?;
+    let key = (msg.bounty_id, &hunter);
+    let bond = APPLICATIONS
+        .may_load(deps.storage, key)?
+        .ok_or(ContractError::NotApplied {})?;
+    APPLICATIONS.remove(deps.storage, key);
 
+    bounty.set_recipient(Some(hunter.clone()));
+    bounty.held_bond = bond;
+    bounty.assignment = bounty
+        .assignment_period
+        .as_ref()
+        .map(|period| period.start(&env));
     BOUNTIES.save(deps.storage, msg.bounty_id, &bounty)?;
 
     Ok(Response::new()
-        .add_message(refund)
-        .add_attribute("action", "expire_bounty")
+        .add_attribute("action", "assign_hunter")
         .add_attribute("bounty_id", msg.bounty_id.to_string())
-        .add_attribute("status", "expired"))
+        .add_attribute("hunter", hunter))
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::GetBounty { bounty_id } => to_binary(&query_bounty(deps, bounty_id)?),
-        QueryMsg::ListBounties {} => to_binary(&query_all_bounties(deps)?),
+/// Give up an assigned bounty, slashing the bond to the issuer and reopening it to applicants
+pub fn execute_abandon(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: AbandonMsg,
+) -> Result<Response, ContractError> {
+    let mut bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
+
+    if bounty.recipient.as_ref() != Some(&info.sender) {
+        return Err(ContractError::NotAssigned {});
     }
+    ensure_bounty_open(&bounty)?;
 
-    pub fn query_bounty(deps: Deps, bounty_id: u64) -> StdResult<Bounty> {
-    let bounty = BOUNTIES.load(deps.storage, bounty_id)?;
-    Ok(bounty)
-}
+    let slashed = bounty.held_bond;
+    let slash = release_bond(deps.storage, &env, &mut bounty, true)?;
+    bounty.set_recipient(None);
+    bounty.assignment = None;
+    BOUNTIES.save(deps.storage, msg.bounty_id, &bounty)?;
 
-pub fn query_all_bounties(deps: Deps) -> StdResult<Vec<Bounty>> {
-    BOUNTIES
-        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
-        .map(|item| item.map(|(_, bounty)| bounty))
-        .collect()
+    Ok(Response::new()
+        .add_submessages(slash)
+        .add_attribute("action", "abandon")
+        .add_attribute("bounty_id", msg.bounty_id.to_string())
+        .add_attribute("hunter", info.sender)
+        .add_attribute("slashed", slashed))
 }
 
-}
+/// Withdraw an application that was not assigned, returning its bond
+pub fn execute_withdraw_application(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: WithdrawApplicationMsg,
+) -> Result<Response, ContractError> {
+    let bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use cosmwasm_std::{
-        coins, from_binary, testing::{mock_dependencies, mock_env, mock_info}, Addr, Uint128,
-    };
-    use crate::msg::{CreateBountyMsg, ExecuteMsg, InstantiateMsg, QueryMsg};
-    use crate::state::{BountyStatus, BOUNTIES, NEXT_BOUNTY_ID};
+    let key = (msg.bounty_id, &info.sender);
+    let bond = APPLICATIONS
+        .may_load(deps.storage, key)?
+        .ok_or(ContractError::NotApplied {})?;
+    APPLICATIONS.remove(deps.storage, key);
 
-    /// Helper function to create a test environment with initialized state
-    fn setup_contract() -> (DepsMut, Env, MessageInfo) {
-        let mut deps = mock_dependencies();
-        let env = mock_env();
-        let info = mock_info("creator", &coins(1000, "token"));
+    Ok(Response::new()
+        .add_messages(pay_out(deps.storage, &bounty, &info.sender, bond)?)
+        .add_attribute("action", "withdraw_application")
+        .add_attribute("bounty_id", msg.bounty_id.to_string())
+        .add_attribute("hunter", info.sender)
+        .add_attribute("bond", bond))
+}
 
-        // Instantiate the contract
-        let msg = InstantiateMsg {};
-        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+/// Submit the work of an assigned hunter, which keeps the assignment past its deadline
+pub fn execute_submit_work(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: SubmitWorkMsg,
+) -> Result<Response, ContractError> {
+    let mut bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
+
+    if bounty.recipient.as_ref() != Some(&info.sender) {
+        return Err(ContractError::NotAssigned {});
+    }
+    ensure_bounty_open(&bounty)?;
+    if check_expired(&bounty, &env) {
+        return Err(ContractError::BountyExpired {});
+    }
+    let config = CONFIG.load(deps.storage)?;
+    if msg.work.len() > config.max_description_length as usize {
+        return Err(ContractError::DescriptionTooLong {
+            max: config.max_description_length,
+        });
+    }
 
-        (deps.as_mut(), env, info)
+    // Hunters named without an assignment period have no deadline to meet
+    let assignment = bounty.assignment.get_or_insert(Assignment {
+        end_height: None,
+        end_time: None,
+        submission: None,
+    });
+    if assignment.submission.is_some() {
+        return Err(ContractError::AlreadySubmitted {});
     }
+    assignment.submission = Some(msg.work);
+    BOUNTIES.save(deps.storage, msg.bounty_id, &bounty)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "submit_work")
+        .add_attribute("bounty_id", msg.bounty_id.to_string())
+        .add_attribute("hunter", info.sender))
 }
 
-#[test]
-fn test_instantiate() {
-    let mut deps = mock_dependencies();
-    let env = mock_env();
-    let info = mock_info("creator", &[]); // No funds required for instantiation
+/// Unassign a hunter who missed their assignment deadline, reopening the bounty to applicants
+pub fn execute_release_assignment(
+    deps: DepsMut,
+    env: Env,
+    msg: ReleaseAssignmentMsg,
+) -> Result<Response, ContractError> {
+    let mut bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
 
-    let msg = InstantiateMsg {};
-    let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    ensure_bounty_open(&bounty)?;
+    let lapsed = bounty
+        .assignment
+        .as_ref()
+        .is_some_and(|assignment| assignment.lapsed(&env));
+    if !lapsed {
+        return Err(ContractError::AssignmentActive {});
+    }
 
-    assert_eq!(res.attributes, vec![
-        ("method", "instantiate"),
-        ("bount_owner", "creator"),
-    ]);
+    let hunter = bounty
+        .recipient
+        .clone()
+        .ok_or(ContractError::RecipientNotSet {})?;
+    let slash = release_bond(deps.storage, &env, &mut bounty, true)?;
+    bounty.set_recipient(None);
+    bounty.assignment = None;
+    BOUNTIES.save(deps.storage, msg.bounty_id, &bounty)?;
 
-    // Ensure the NEXT_BOUNTY_ID is initialized to 1
-    let id = NEXT_BOUNTY_ID.load(deps.as_ref().storage).unwrap();
-    assert_eq!(id, 1u64);
+    Ok(Response::new()
+        .add_submessages(slash)
+        .add_attribute("action", "release_assignment")
+        .add_attribute("bounty_id", msg.bounty_id.to_string())
+        .add_attribute("hunter", hunter))
 }
 
-#[test]
-fn test_create_bounty() {
-    let (deps, env, info) = setup_contract();
+/// Finalize a bounty
+pub fn execute_finalize_bounty(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: FinalizeBountyMsg,
+) -> Result<Response, ContractError> {
+    let bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
 
-    let create_msg = ExecuteMsg::CreateBounty(CreateBountyMsg {
-        title: "Fix a bug".to_string(),
-        description: "Fix a critical bug in the system".to_string(),
-        recipient: Some("developer".to_string()),
-        end_height: Some(env.block.height + 100),
-        end_time: None,
-        token_denom: "token".to_string(),
-        quantity: Uint128::new(500),
-    });
+    if !bounty.reviewers.is_empty() {
+        return vote_on_bounty(deps, env, info, bounty, msg);
+    }
 
-    // Simulate sending the required funds
-    let info = mock_info("creator", &coins(500, "token"));
-    let res = execute(deps, env.clone(), info.clone(), create_msg).unwrap();
-
-    assert_eq!(res.attributes, vec![
-        ("action", "create_bounty"),
-        ("bounty_id", "1"),
-        ("issuer", "creator"),
-    ]);
-
-    // Verify the bounty is stored correctly
-    let bounty = BOUNTIES.load(deps.storage, 1).unwrap();
-    assert_eq!(bounty.title, "Fix a bug");
-    assert_eq!(bounty.description, "Fix a critical bug in the system");
-    assert_eq!(bounty.recipient.unwrap(), Addr::unchecked("developer"));
-    assert_eq!(bounty.balance, Uint128::new(500));
-    assert_eq!(bounty.status, BountyStatus::Open);
-}
-#[test]
-fn test_finalize_bounty_success() {
-    let (deps, env, info) = setup_contract();
-
-    // Create a bounty first
-    let create_msg = ExecuteMsg::CreateBounty(CreateBountyMsg {
-        title: "Fix a bug".to_string(),
-        description: "Fix a critical bug in the system".to_string(),
-        recipient: Some("developer".to_string()),
-        end_height: Some(env.block.height + 100),
-        end_time: None,
-        token_denom: "token".to_string(),
-        quantity: Uint128::new(500),
-    });
-    let info = mock_info("creator", &coins(500, "token"));
-    execute(deps, env.clone(), info.clone(), create_msg).unwrap();
+    ensure_issuer(deps.as_ref(), &bounty, &info.sender)?;
 
-    // Finalize the bounty successfully
-    let finalize_msg = ExecuteMsg::FinalizeBounty(FinalizeBountyMsg {
-        bounty_id: 1,
-        success: true,
-    });
+    finalize_bounty(deps, &env, bounty, msg)
+}
 
-    let info = mock_info("creator", &[]);
-    let res = execute(deps, env.clone(), info, finalize_msg).unwrap();
-
-    assert_eq!(res.attributes, vec![
-        ("action", "finalize_bounty"),
-        ("bounty_id", "1"),
-        ("status", "completed"),
-    ]);
-
-    // Verify the bounty status
-    let bounty = BOUNTIES.load(deps.storage, 1).unwrap();
-    assert_eq!(bounty.status, BountyStatus::Completed);
-}
-#[test]
-fn test_expire_bounty() {
-    let (deps, env, info) = setup_contract();
-
-    // Create a bounty
-    let create_msg = ExecuteMsg::CreateBounty(CreateBountyMsg {
-        title: "Write documentation".to_string(),
-        description: "Write detailed docs for the project".to_string(),
-        recipient: None,
-        end_height: Some(env.block.height + 1), // Immediate expiration
-        end_time: None,
-        token_denom: "token".to_string(),
-        quantity: Uint128::new(300),
-    });
-    let info = mock_info("creator", &coins(300, "token"));
-    execute(deps, env.clone(), info.clone(), create_msg).unwrap();
-
-    // Advance the block height to simulate expiration
-    let mut env = env.clone();
-    env.block.height += 10;
-
-    let expire_msg = ExecuteMsg::ExpireBounty(ExpireBountyMsg { bounty_id: 1 });
-    let info = mock_info("creator", &[]);
-    let res = execute(deps, env, info, expire_msg).unwrap();
-
-    assert_eq!(res.attributes, vec![
-        ("action", "expire_bounty"),
-        ("bounty_id", "1"),
-        ("status", "expired"),
-    ]);
-
-    // Verify the bounty status
-    let bounty = BOUNTIES.load(deps.storage, 1).unwrap();
-    assert_eq!(bounty.status, BountyStatus::Expired);
-}
-#[test]
-fn test_query_all_bounties() {
-    let (deps, env, info) = setup_contract();
-
-    // Create multiple bounties
-    for i in 1..=3 {
-        let create_msg = ExecuteMsg::CreateBounty(CreateBountyMsg {
-            title: format!("Bounty {}", i),
-            description: "Do something important".to_string(),
-            recipient: None,
-            end_height: None,
-            end_time: None,
-            token_denom: "token".to_string(),
-            quantity: Uint128::new(100 * i),
-        });
-        let info = mock_info("creator", &coins(100 * i, "token"));
-        execute(deps, env.clone(), info.clone(), create_msg).unwrap();
+/// Record a reviewer's vote, finalizing the bounty once either side reaches the threshold
+fn vote_on_bounty(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    bounty: Bounty,
+    msg: FinalizeBountyMsg,
+) -> Result<Response, ContractError> {
+    if !bounty.reviewers.contains(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    ensure_bounty_open(&bounty)?;
+
+    let (bounty_id, approve) = (msg.bounty_id, msg.success);
+    let key = (bounty_id, &info.sender);
+    if VOTES.has(deps.storage, key) {
+        return Err(ContractError::AlreadyVoted {});
     }
+    VOTES.save(deps.storage, key, &approve)?;
+
+    let tally = query_votes(deps.as_ref(), bounty_id)?;
+    let decisive = if approve {
+        tally.approvals
+    } else {
+        tally.rejections
+    };
 
-    let res = query(deps.as_ref(), env, QueryMsg::ListBounties {}).unwrap();
-    let bounties: Vec<Bounty> = from_binary(&res).unwrap();
+    let res = if decisive >= bounty.threshold {
+        finalize_bounty(deps, &env, bounty, msg)?
+    } else {
+        Response::new()
+            .add_attribute("action", "finalize_bounty")
+            .add_attribute("bounty_id", bounty_id.to_string())
+    };
 
-    assert_eq!(bounties.len(), 3);
-    assert_eq!(bounties[0].title, "Bounty 1");
-    assert_eq!(bounties[1].title, "Bounty 2");
-    assert_eq!(bounties[2].title, "Bounty 3");
+    Ok(res
+        .add_attribute("reviewer", info.sender.to_string())
+        .add_attribute("approve", approve.to_string())
+        .add_attribute("approvals", tally.approvals.to_string())
+        .add_attribute("rejections", tally.rejections.to_string()))
 }
 
+/// Finalize a bounty on behalf of an already authorized caller
+pub(crate) fn finalize_bounty(
+    deps: DepsMut,
+    env: &Env,
+    mut bounty: Bounty,
+    msg: FinalizeBountyMsg,
+) -> Result<Response, ContractError> {
+    ensure_single_payout(&bounty)?;
+    ensure_unsettled(&bounty)?;
+
+    if check_expired(&bounty, env) {
+        bounty.status = BountyStatus::Expired;
+    }
 
+    if msg.success {
+        let recipient = bounty
+            .recipient
+            .clone()
+            .ok_or(ContractError::RecipientNotSet {})?;
+        let amount = bounty.balance;
+        approve_payout(
+            deps,
+            env,
+            msg.bounty_id,
+            bounty,
+            recipient,
+            amount,
+            "finalize_bounty",
+        )
+    } else {
+        refund_bounty(deps, env, msg.bounty_id, bounty, "finalize_bounty")
+    }
+}
 
+/// Let a bounty's recipient have its payout delivered to an account on another chain
+pub fn execute_set_payout_destination(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: SetPayoutDestinationMsg,
+) -> Result<Response, ContractError> {
+    let mut bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
 
+    if bounty.recipient.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    if matches!(
+        bounty.status,
+        BountyStatus::Completed | BountyStatus::Expired
+    ) {
+        return Err(ContractError::BountyClosed {});
+    }
+    if let Some(destination) = &msg.ibc {
+        validate_ibc_destination(destination)?;
+        if bounty.vesting.is_some() {
+            return Err(ContractError::IbcPayoutUnsupported {});
+        }
+    }
 
+    bounty.payout_destination = msg.ibc;
+    BOUNTIES.save(deps.storage, msg.bounty_id, &bounty)?;
 
+    Ok(Response::new()
+        .add_attribute("action", "set_payout_destination")
+        .add_attribute("bounty_id", msg.bounty_id.to_string())
+        .add_attribute("recipient", info.sender))
+}
 
+/// Finalize a bounty with an approval signed off-chain by the bounty's approver key
+pub fn execute_finalize_with_signature(
+    deps: DepsMut,
+    env: Env,
+    msg: SignedFinalizeMsg,
+) -> Result<Response, ContractError> {
+    let mut bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
 
+    ensure_bounty_open(&bounty)?;
+    if check_expired(&bounty, &env) {
+        return Err(ContractError::BountyExpired {});
+    }
 
+    let key = bounty
+        .approver_key
+        .clone()
+        .ok_or(ContractError::ApproverKeyNotSet {})?;
+    if msg.nonce != bounty.nonce {
+        return Err(ContractError::InvalidNonce {
+            expected: bounty.nonce,
+        });
+    }
 
+    let recipient = deps.api.addr_validate(&msg.recipient)?;
+    if bounty.recipient.as_ref().is_some_and(|r| *r != recipient) {
+        return Err(ContractError::RecipientMismatch {});
+    }
+    if msg.amount > bounty.balance {
+        return Err(ContractError::InsufficientFunds {});
+    }
 
+    let payload = ApprovalPayload {
+        contract_address: env.contract.address.clone(),
+        chain_id: env.block.chain_id.clone(),
+        bounty_id: msg.bounty_id,
+        recipient: recipient.clone(),
+        amount: msg.amount,
+        nonce: msg.nonce,
+    };
+    verify_approval(deps.api, &key, &payload, &msg.signature)?;
 
+    bounty.nonce += 1;
 
+    approve_payout(
+        deps,
+        &env,
+        msg.bounty_id,
+        bounty,
+        recipient,
+        msg.amount,
+        "finalize_with_signature",
+    )
+}
 
+/// Record an oracle attestation that the bounty was completed, starting the issuer's veto window
+pub fn execute_attest_completion(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: AttestCompletionMsg,
+) -> Result<Response, ContractError> {
+    let mut bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
 
+    if bounty.oracle.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    if bounty.attestation.is_some() {
+        return Err(ContractError::AttestationPending {});
+    }
+    ensure_single_payout(&bounty)?;
+    ensure_bounty_open(&bounty)?;
+    if check_expired(&bounty, &env) {
+        return Err(ContractError::BountyExpired {});
+    }
 
+    let recipient = deps.api.addr_validate(&msg.recipient)?;
+    if bounty.recipient.as_ref().is_some_and(|r| *r != recipient) {
+        return Err(ContractError::RecipientMismatch {});
+    }
 
+    let executable_at = env.block.time.plus_seconds(bounty.veto_period);
+    bounty.status = BountyStatus::InProgress;
+    bounty.attestation = Some(Attestation {
+        recipient: recipient.clone(),
+        evidence: msg.evidence,
+        executable_at,
+    });
 
+    BOUNTIES.save(deps.storage, msg.bounty_id, &bounty)?;
 
+    Ok(Response::new()
+        .add_attribute("action", "attest_completion")
+        .add_attribute("bounty_id", msg.bounty_id.to_string())
+        .add_attribute("recipient", recipient)
+        .add_attribute("executable_at", executable_at.to_string()))
+}
 
+/// Reject a pending oracle attestation while its veto window is open
+pub fn execute_veto_attestation(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: VetoAttestationMsg,
+) -> Result<Response, ContractError> {
+    let mut bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
 
+    ensure_issuer(deps.as_ref(), &bounty, &info.sender)?;
 
+    let attestation = bounty
+        .attestation
+        .take()
+        .ok_or(ContractError::NoAttestation {})?;
+    if env.block.time >= attestation.executable_at {
+        return Err(ContractError::VetoWindowClosed {});
+    }
 
+    bounty.status = BountyStatus::Open;
 
+    BOUNTIES.save(deps.storage, msg.bounty_id, &bounty)?;
 
+    let hook = BountyHookMsg::Disputed {
+        bounty_id: msg.bounty_id,
+    };
+    Ok(Response::new()
+        .add_submessages(prepare_hooks(deps.storage, &bounty, hook)?)
+        .add_attribute("action", "veto_attestation")
+        .add_attribute("bounty_id", msg.bounty_id.to_string()))
+}
+
+/// Pay out an oracle attestation once the veto window has passed
+pub fn execute_execute_attestation(
+    deps: DepsMut,
+    env: Env,
+    msg: ExecuteAttestationMsg,
+) -> Result<Response, ContractError> {
+    let mut bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
+
+    // An attestation only stands while the bounty it attests to is still in progress
+    if bounty.status != BountyStatus::InProgress {
+        return Err(ContractError::NoAttestation {});
+    }
+    let attestation = bounty
+        .attestation
+        .take()
+        .ok_or(ContractError::NoAttestation {})?;
+    if env.block.time < attestation.executable_at {
+        return Err(ContractError::VetoWindowOpen {});
+    }
+
+    // Attested payouts are held and frozen like any other approval
+    let amount = bounty.balance;
+    approve_payout(
+        deps,
+        &env,
+        msg.bounty_id,
+        bounty,
+        attestation.recipient,
+        amount,
+        "execute_attestation",
+    )
+}
+
+/// Expire a bounty
+pub fn execute_expire_bounty(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExpireBountyMsg,
+) -> Result<Response, ContractError> {
+    let bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
+
+    ensure_issuer(deps.as_ref(), &bounty, &info.sender)?;
+
+    // Security pools return what is left of the pool on expiry, and so do contests nobody
+    // settled in time
+    let unsettled = bounty
+        .contest
+        .as_ref()
+        .is_some_and(|contest| contest.settle_ended(&env));
+    if bounty.security.is_none() && !unsettled {
+        ensure_single_payout(&bounty)?;
+    }
+    ensure_unsettled(&bounty)?;
+
+    if !check_expired(&bounty, &env) {
+        return Err(ContractError::NotYetExpired {});
+    }
+
+    refund_bounty(deps, &env, msg.bounty_id, bounty, "expire_bounty")
+}
+
+/// Complete an issuer-approved payout, or hold it for the bounty's release delay
+fn approve_payout(
+    deps: DepsMut,
+    env: &Env,
+    bounty_id: u64,
+    mut bounty: Bounty,
+    recipient: Addr,
+    amount: Uint128,
+    action: &str,
+) -> Result<Response, ContractError> {
+    ensure_single_payout(&bounty)?;
+    ensure_liquid(&bounty, env)?;
+    let amount = pegged_amount(deps.as_ref(), &bounty, amount)?;
+    let Some(delay) = bounty.release_delay else {
+        return complete_bounty(deps, env, bounty_id, bounty, recipient, amount, action);
+    };
+
+    let remainder = bounty.balance - amount;
+    let release_at = env.block.time.plus_seconds(delay);
+
+    bounty.status = BountyStatus::PendingRelease { release_at };
+    bounty.set_recipient(Some(recipient.clone()));
+    bounty.balance = amount;
+    bounty.attestation = None;
+
+    let refund = refund_issuer(deps.storage, env, &bounty, remainder)?;
+
+    BOUNTIES.save(deps.storage, bounty_id, &bounty)?;
+
+    Ok(Response::new()
+        .add_submessages(refund)
+        .add_attribute("action", action)
+        .add_attribute("bounty_id", bounty_id.to_string())
+        .add_attribute("status", "pending_release")
+        .add_attribute("release_at", release_at.to_string()))
+}
+
+/// Cap `amount` at the bounty's USD value in its denom at the price oracle's current price
+///
+/// Pegged bounties pay their USD value, the rest going back to the issuer.
+fn pegged_amount(deps: Deps, bounty: &Bounty, amount: Uint128) -> Result<Uint128, ContractError> {
+    let Some(usd_value) = bounty.usd_value else {
+        return Ok(amount);
+    };
+    let oracle = CONFIG
+        .load(deps.storage)?
+        .price_oracle
+        .ok_or(ContractError::PriceOracleNotSet {})?;
+    let res: PriceResponse = deps.querier.query_wasm_smart(
+        oracle,
+        &OracleQueryMsg::Price {
+            denom: bounty.token_denom.clone(),
+        },
+    )?;
+    let pegged = usd_value
+        .checked_div_floor(res.price)
+        .map_err(|_| ContractError::InvalidPrice {})?;
+    Ok(amount.min(pegged))
+}
+
+/// Claim a held payout once its release time has passed
+pub fn execute_claim_payout(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ClaimPayoutMsg,
+) -> Result<Response, ContractError> {
+    let bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
+
+    let BountyStatus::PendingRelease { release_at } = bounty.status else {
+        return Err(ContractError::NotPendingRelease {});
+    };
+    let recipient = bounty
+        .recipient
+        .clone()
+        .ok_or(ContractError::RecipientNotSet {})?;
+    if info.sender != recipient {
+        return Err(ContractError::Unauthorized {});
+    }
+    if env.block.time < release_at {
+        return Err(ContractError::NotYetReleased { release_at });
+    }
+
+    let amount = bounty.balance;
+    complete_bounty(
+        deps,
+        &env,
+        msg.bounty_id,
+        bounty,
+        recipient,
+        amount,
+        "claim_payout",
+    )
+}
+
+/// Freeze a held payout before its release time
+pub fn execute_freeze_payout(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: FreezePayoutMsg,
+) -> Result<Response, ContractError> {
+    let mut bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
+    ensure_arbiter(deps.as_ref(), &bounty, &info)?;
+
+    let BountyStatus::PendingRelease { release_at } = bounty.status else {
+        return Err(ContractError::NotPendingRelease {});
+    };
+    if env.block.time >= release_at {
+        return Err(ContractError::ChallengeWindowClosed {});
+    }
+
+    bounty.status = BountyStatus::Frozen;
+
+    BOUNTIES.save(deps.storage, msg.bounty_id, &bounty)?;
+
+    let hook = BountyHookMsg::Disputed {
+        bounty_id: msg.bounty_id,
+    };
+    Ok(Response::new()
+        .add_submessages(prepare_hooks(deps.storage, &bounty, hook)?)
+        .add_attribute("action", "freeze_payout")
+        .add_attribute("bounty_id", msg.bounty_id.to_string()))
+}
+
+/// Release a frozen payout to the recipient or refund it to the issuer
+pub fn execute_resolve_frozen_payout(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ResolveFrozenPayoutMsg,
+) -> Result<Response, ContractError> {
+    let bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
+    ensure_arbiter(deps.as_ref(), &bounty, &info)?;
+
+    if bounty.status != BountyStatus::Frozen {
+        return Err(ContractError::NotFrozen {});
+    }
+
+    if msg.release {
+        let recipient = bounty
+            .recipient
+            .clone()
+            .ok_or(ContractError::RecipientNotSet {})?;
+        let amount = bounty.balance;
+        complete_bounty(
+            deps,
+            &env,
+            msg.bounty_id,
+            bounty,
+            recipient,
+            amount,
+            "resolve_frozen_payout",
+        )
+    } else {
+        refund_bounty(deps, &env, msg.bounty_id, bounty, "resolve_frozen_payout")
+    }
+}
+
+/// Submit an entry to a contest bounty before its deadline
+pub fn execute_submit_entry(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: SubmitEntryMsg,
+) -> Result<Response, ContractError> {
+    let bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
+    let contest = bounty
+        .contest
+        .as_ref()
+        .ok_or(ContractError::NotContest {})?;
+
+    ensure_bounty_open(&bounty)?;
+    if check_expired(&bounty, &env) {
+        return Err(ContractError::SubmissionsClosed {});
+    }
+    if contest.commit_reveal() {
+        return Err(ContractError::CommitRevealRequired {});
+    }
+
+    let entry_id = add_entry(deps, msg.bounty_id, bounty, &info.sender, msg.content)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "submit_entry")
+        .add_attribute("bounty_id", msg.bounty_id.to_string())
+        .add_attribute("entry_id", entry_id.to_string())
+        .add_attribute("hunter", info.sender))
+}
+
+/// Commit to the hash of an entry of a commit-reveal contest before its deadline
+pub fn execute_commit_submission(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: CommitSubmissionMsg,
+) -> Result<Response, ContractError> {
+    let bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
+    let contest = bounty
+        .contest
+        .as_ref()
+        .ok_or(ContractError::NotContest {})?;
+
+    ensure_bounty_open(&bounty)?;
+    if check_expired(&bounty, &env) {
+        return Err(ContractError::SubmissionsClosed {});
+    }
+    if !contest.commit_reveal() {
+        return Err(ContractError::NotCommitReveal {});
+    }
+    if msg.hash.len() != 32 {
+        return Err(ContractError::InvalidCommitment {});
+    }
+
+    // A later commitment replaces the earlier one
+    COMMITMENTS.save(deps.storage, (msg.bounty_id, &info.sender), &msg.hash)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "commit_submission")
+        .add_attribute("bounty_id", msg.bounty_id.to_string())
+        .add_attribute("hunter", info.sender))
+}
+
+/// Reveal a committed entry after the deadline, entering it into the contest.
+///
+/// Commitments left unrevealed when the reveal period ends never become entries.
+pub fn execute_reveal_submission(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: RevealSubmissionMsg,
+) -> Result<Response, ContractError> {
+    let bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
+    let contest = bounty
+        .contest
+        .as_ref()
+        .ok_or(ContractError::NotContest {})?;
+
+    ensure_bounty_open(&bounty)?;
+    if !contest.commit_reveal() {
+        return Err(ContractError::NotCommitReveal {});
+    }
+    if !check_expired(&bounty, &env) || contest.reveal_ended(&env) {
+        return Err(ContractError::RevealClosed {});
+    }
+
+    let key = (msg.bounty_id, &info.sender);
+    let commitment = COMMITMENTS
+        .may_load(deps.storage, key)?
+        .ok_or(ContractError::NoCommitment {})?;
+    let hash = Sha256::new()
+        .chain_update(info.sender.as_bytes())
+        .chain_update(msg.salt.as_bytes())
+        .chain_update(msg.payload.as_bytes())
+        .finalize();
+    if commitment.as_slice() != hash.as_slice() {
+        return Err(ContractError::CommitmentMismatch {});
+    }
+    COMMITMENTS.remove(deps.storage, key);
+
+    let entry_id = add_entry(deps, msg.bounty_id, bounty, &info.sender, msg.payload)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "reveal_submission")
+        .add_attribute("bounty_id", msg.bounty_id.to_string())
+        .add_attribute("entry_id", entry_id.to_string())
+        .add_attribute("hunter", info.sender))
+}
+
+/// Store the entry of `hunter` in a contest bounty, returning its ID.
+///
+/// Each hunter enters once, so the entry limit can't be filled by a single address.
+fn add_entry(
+    deps: DepsMut,
+    bounty_id: u64,
+    mut bounty: Bounty,
+    hunter: &Addr,
+    content: String,
+) -> Result<u64, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if content.len() > config.max_description_length as usize {
+        return Err(ContractError::DescriptionTooLong {
+            max: config.max_description_length,
+        });
+    }
+
+    let contest = bounty
+        .contest
+        .as_mut()
+        .ok_or(ContractError::NotContest {})?;
+    if ENTRANTS.has(deps.storage, (bounty_id, hunter)) {
+        return Err(ContractError::AlreadyEntered {});
+    }
+    if contest.entry_count >= MAX_CONTEST_ENTRIES {
+        return Err(ContractError::TooManyEntries {
+            max: MAX_CONTEST_ENTRIES,
+        });
+    }
+    contest.entry_count += 1;
+    let entry_id = contest.entry_count;
+
+    let entry = Entry {
+        hunter: hunter.clone(),
+        content,
+        votes: Uint128::zero(),
+    };
+    ENTRIES.save(deps.storage, (bounty_id, entry_id), &entry)?;
+    ENTRANTS.save(deps.storage, (bounty_id, hunter), &entry_id)?;
+    BOUNTIES.save(deps.storage, bounty_id, &bounty)?;
+
+    Ok(entry_id)
+}
+
+/// Vote for a contest entry once entries are closed and revealed, until the end of voting
+pub fn execute_vote_entry(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: VoteEntryMsg,
+) -> Result<Response, ContractError> {
+    let bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
+    let contest = bounty
+        .contest
+        .as_ref()
+        .ok_or(ContractError::NotContest {})?;
+
+    ensure_bounty_open(&bounty)?;
+    if !check_expired(&bounty, &env) || !contest.reveal_ended(&env) || contest.voting_ended(&env) {
+        return Err(ContractError::VotingClosed {});
+    }
+
+    let key = (msg.bounty_id, &info.sender);
+    if ENTRY_VOTES.has(deps.storage, key) {
+        return Err(ContractError::AlreadyVoted {});
+    }
+
+    let power = voting_power(deps.as_ref(), contest, &info.sender)?;
+    if power.is_zero() {
+        return Err(ContractError::NoVotingPower {});
+    }
+
+    let mut entry = ENTRIES.load(deps.storage, (msg.bounty_id, msg.entry_id))?;
+    entry.votes += power;
+    ENTRIES.save(deps.storage, (msg.bounty_id, msg.entry_id), &entry)?;
+    ENTRY_VOTES.save(deps.storage, key, &msg.entry_id)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "vote_entry")
+        .add_attribute("bounty_id", msg.bounty_id.to_string())
+        .add_attribute("entry_id", msg.entry_id.to_string())
+        .add_attribute("voter", info.sender)
+        .add_attribute("power", power))
+}
+
+/// Voting power of `voter` in a contest
+fn voting_power(deps: Deps, contest: &Contest, voter: &Addr) -> StdResult<Uint128> {
+    match &contest.voters {
+        Voters::Whitelist(voters) if voters.contains(voter) => Ok(Uint128::one()),
+        Voters::Whitelist(_) => Ok(Uint128::zero()),
+        Voters::Staking(contract) => {
+            let res: VotingPowerAtHeightResponse = deps.querier.query_wasm_smart(
+                contract,
+                &StakingQueryMsg::VotingPowerAtHeight {
+                    address: voter.to_string(),
+                    height: Some(contest.snapshot_height),
+                },
+            )?;
+            Ok(res.power)
+        }
+    }
+}
+
+/// Rank the entries of a contest by votes once voting ended and pay out the prize table.
+///
+/// Ties go to the earlier entry. Entries without votes win nothing, and unawarded prizes go
+/// back to the issuer, together with the NFT prizes when nobody won. NFT prizes otherwise go
+/// to the winner.
+pub fn execute_settle_contest(
+    deps: DepsMut,
+    env: Env,
+    msg: SettleContestMsg,
+) -> Result<Response, ContractError> {
+    let mut bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
+    let contest = bounty.contest.clone().ok_or(ContractError::NotContest {})?;
+
+    ensure_bounty_open(&bounty)?;
+    if !contest.voting_ended(&env) {
+        return Err(ContractError::VotingNotEnded {});
+    }
+
+    let mut ranking: Vec<(u64, Entry)> = ENTRIES
+        .prefix(msg.bounty_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|item| !matches!(item, Ok((_, entry)) if entry.votes.is_zero()))
+        .collect::<StdResult<_>>()?;
+    // stable sort keeps earlier entries first on ties
+    ranking.sort_by_key(|(_, entry)| Reverse(entry.votes));
+
+    let Some((_, winner)) = ranking.first() else {
+        return refund_bounty(deps, &env, msg.bounty_id, bounty, "settle_contest");
+    };
+    let winner = winner.hunter.clone();
+
+    let mut res = Response::new();
+    let mut hooks = vec![];
+    let mut paid = Uint128::zero();
+    for ((entry_id, entry), prize) in ranking.iter().zip(&contest.prizes) {
+        res = res
+            .add_messages(pay_out(deps.storage, &bounty, &entry.hunter, *prize)?)
+            .add_attribute(format!("entry_{entry_id}"), prize.to_string());
+        hooks.push(BountyHookMsg::Completed {
+            bounty_id: msg.bounty_id,
+            recipient: entry.hunter.clone(),
+            amount: Coin {
+                denom: bounty.token_denom.clone(),
+                amount: *prize,
+            },
+        });
+        paid += *prize;
+    }
+    let remainder = bounty.balance - paid;
+
+    bounty.status = BountyStatus::Completed;
+    bounty.recipient = Some(winner.clone());
+    bounty.balance = Uint128::zero();
+
+    res = res
+        .add_submessages(refund_issuer(deps.storage, &env, &bounty, remainder)?)
+        .add_submessages(transfer_nfts(&bounty.nft_rewards, &winner)?);
+
+    BOUNTIES.save(deps.storage, msg.bounty_id, &bounty)?;
+
+    if let Some(badge_contract) = CONFIG.load(deps.storage)?.badge_contract {
+        let prize = Coin {
+            denom: bounty.token_denom.clone(),
+            amount: contest.prizes[0],
+        };
+        res = res.add_message(mint_badge(
+            &badge_contract,
+            msg.bounty_id,
+            &bounty,
+            &winner,
+            prize,
+        )?);
+    }
+    for hook in hooks {
+        res = res.add_submessages(prepare_hooks(deps.storage, &bounty, hook)?);
+    }
+
+    Ok(res
+        .add_attribute("action", "settle_contest")
+        .add_attribute("bounty_id", msg.bounty_id.to_string())
+        .add_attribute("winner", winner)
+        .add_attribute("status", "completed"))
+}
+
+/// Submit a report to a severity-tiered bounty before its deadline
+pub fn execute_submit_report(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: SubmitReportMsg,
+) -> Result<Response, ContractError> {
+    let mut bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
+
+    ensure_bounty_open(&bounty)?;
+    if check_expired(&bounty, &env) {
+        return Err(ContractError::SubmissionsClosed {});
+    }
+    let config = CONFIG.load(deps.storage)?;
+    if msg.content.len() > config.max_description_length as usize {
+        return Err(ContractError::DescriptionTooLong {
+            max: config.max_description_length,
+        });
+    }
+
+    let security = bounty
+        .security
+        .as_mut()
+        .ok_or(ContractError::NotSecurityPool {})?;
+    security.report_count += 1;
+    let report_id = security.report_count;
+
+    let report = Report {
+        hunter: info.sender.clone(),
+        content: msg.content,
+        status: ReportStatus::Pending,
+    };
+    REPORTS.save(deps.storage, (msg.bounty_id, report_id), &report)?;
+    BOUNTIES.save(deps.storage, msg.bounty_id, &bounty)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "submit_report")
+        .add_attribute("bounty_id", msg.bounty_id.to_string())
+        .add_attribute("report_id", report_id.to_string())
+        .add_attribute("hunter", info.sender))
+}
+
+/// Rate a report, paying the hunter its severity tier from the pool.
+///
+/// The last payouts are capped at what is left in the pool, which completes the bounty once empty.
+pub fn execute_rate_report(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: RateReportMsg,
+) -> Result<Response, ContractError> {
+    let mut bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
+    let tiers = bounty
+        .security
+        .as_ref()
+        .ok_or(ContractError::NotSecurityPool {})?
+        .tiers
+        .clone();
+
+    if !bounty.reviewers.contains(&info.sender) {
+        ensure_issuer(deps.as_ref(), &bounty, &info.sender)?;
+    }
+    ensure_bounty_open(&bounty)?;
+
+    let key = (msg.bounty_id, msg.report_id);
+    let mut report = REPORTS.load(deps.storage, key)?;
+    if report.status != ReportStatus::Pending {
+        return Err(ContractError::AlreadyRated {});
+    }
+
+    let Some(severity) = msg.severity else {
+        report.status = ReportStatus::Rejected;
+        REPORTS.save(deps.storage, key, &report)?;
+        return Ok(Response::new()
+            .add_attribute("action", "rate_report")
+            .add_attribute("bounty_id", msg.bounty_id.to_string())
+            .add_attribute("report_id", msg.report_id.to_string())
+            .add_attribute("status", "rejected"));
+    };
+
+    let paid = tiers.amount(&severity).min(bounty.balance);
+    bounty.balance -= paid;
+    if bounty.balance.is_zero() {
+        bounty.status = BountyStatus::Completed;
+    }
+    report.status = ReportStatus::Accepted { severity, paid };
+
+    let payout = pay_out(deps.storage, &bounty, &report.hunter, paid)?;
+    REPORTS.save(deps.storage, key, &report)?;
+    BOUNTIES.save(deps.storage, msg.bounty_id, &bounty)?;
+
+    let hook = BountyHookMsg::Completed {
+        bounty_id: msg.bounty_id,
+        recipient: report.hunter.clone(),
+        amount: Coin {
+            denom: bounty.token_denom.clone(),
+            amount: paid,
+        },
+    };
+    Ok(Response::new()
+        .add_messages(payout)
+        .add_submessages(prepare_hooks(deps.storage, &bounty, hook)?)
+        .add_attribute("action", "rate_report")
+        .add_attribute("bounty_id", msg.bounty_id.to_string())
+        .add_attribute("report_id", msg.report_id.to_string())
+        .add_attribute("hunter", report.hunter)
+        .add_attribute("paid", paid))
+}
+
+/// Contests, Merkle distributions and security pools pay out on their own terms, not to a
+/// single recipient
+fn ensure_single_payout(bounty: &Bounty) -> Result<(), ContractError> {
+    if bounty.contest.is_some() {
+        return Err(ContractError::ContestBounty {});
+    }
+    if bounty.security.is_some() {
+        return Err(ContractError::SecurityPool {});
+    }
+    if bounty.merkle.is_some() {
+        return Err(ContractError::MerkleDistribution {});
+    }
+    Ok(())
+}
+
+/// Only open or in-progress bounties may still be paid out or refunded
+fn ensure_unsettled(bounty: &Bounty) -> Result<(), ContractError> {
+    match bounty.status {
+        BountyStatus::Open | BountyStatus::InProgress => Ok(()),
+        BountyStatus::PendingRelease { .. } | BountyStatus::Frozen => {
+            Err(ContractError::PayoutHeld {})
+        }
+        BountyStatus::Completed | BountyStatus::Expired => Err(ContractError::BountyClosed {}),
+    }
+}
+
+/// Post the Merkle root of a bounty's payouts, letting each recipient claim their share
+pub fn execute_post_merkle_root(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: PostMerkleRootMsg,
+) -> Result<Response, ContractError> {
+    let mut bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
+
+    ensure_issuer(deps.as_ref(), &bounty, &info.sender)?;
+    ensure_single_payout(&bounty)?;
+    ensure_bounty_open(&bounty)?;
+    if bounty.recurrence.is_some() {
+        return Err(ContractError::InvalidRecurrence {});
+    }
+    if !bounty.held_bond.is_zero() {
+        return Err(ContractError::HunterAssigned {});
+    }
+    ensure_liquid(&bounty, &env)?;
+    if msg.root.len() != 32 || msg.claim_deadline <= env.block.time {
+        return Err(ContractError::InvalidMerkleRoot {});
+    }
+
+    bounty.status = BountyStatus::InProgress;
+    bounty.merkle = Some(MerkleDistribution {
+        root: msg.root.clone(),
+        claim_deadline: msg.claim_deadline,
+    });
+
+    BOUNTIES.save(deps.storage, msg.bounty_id, &bounty)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "post_merkle_root")
+        .add_attribute("bounty_id", msg.bounty_id.to_string())
+        .add_attribute("root", msg.root.to_base64())
+        .add_attribute("claim_deadline", msg.claim_deadline.to_string()))
+}
+
+/// Claim the sender's payout from a bounty's Merkle root before the claim deadline
+pub fn execute_claim_merkle(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ClaimMerkleMsg,
+) -> Result<Response, ContractError> {
+    let mut bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
+    let merkle = bounty
+        .merkle
+        .as_ref()
+        .ok_or(ContractError::NoMerkleRoot {})?;
+
+    if env.block.time > merkle.claim_deadline {
+        return Err(ContractError::ClaimDeadlinePassed {});
+    }
+    let key = (msg.bounty_id, &info.sender);
+    if MERKLE_CLAIMED.has(deps.storage, key) {
+        return Err(ContractError::AlreadyClaimed {});
+    }
+    if !verify_proof(
+        &merkle.root,
+        leaf_hash(&info.sender, msg.amount),
+        &msg.proof,
+    ) {
+        return Err(ContractError::InvalidProof {});
+    }
+    if msg.amount > bounty.balance {
+        return Err(ContractError::InsufficientFunds {});
+    }
+
+    MERKLE_CLAIMED.save(deps.storage, key, &true)?;
+    bounty.balance -= msg.amount;
+    let payout = pay_out(deps.storage, &bounty, &info.sender, msg.amount)?;
+
+    BOUNTIES.save(deps.storage, msg.bounty_id, &bounty)?;
+
+    let hook = BountyHookMsg::Completed {
+        bounty_id: msg.bounty_id,
+        recipient: info.sender.clone(),
+        amount: Coin {
+            denom: bounty.token_denom.clone(),
+            amount: msg.amount,
+        },
+    };
+    Ok(Response::new()
+        .add_messages(payout)
+        .add_submessages(prepare_hooks(deps.storage, &bounty, hook)?)
+        .add_attribute("action", "claim_merkle")
+        .add_attribute("bounty_id", msg.bounty_id.to_string())
+        .add_attribute("recipient", info.sender)
+        .add_attribute("amount", msg.amount))
+}
+
+/// Return what was not claimed from a Merkle distribution to the issuer once the deadline passed
+pub fn execute_sweep_merkle(
+    deps: DepsMut,
+    env: Env,
+    msg: SweepMerkleMsg,
+) -> Result<Response, ContractError> {
+    let mut bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
+    let merkle = bounty
+        .merkle
+        .as_ref()
+        .ok_or(ContractError::NoMerkleRoot {})?;
+
+    if bounty.status != BountyStatus::InProgress {
+        return Err(ContractError::NothingToClaim {});
+    }
+    if env.block.time <= merkle.claim_deadline {
+        return Err(ContractError::ClaimDeadlineNotPassed {});
+    }
+
+    let swept = bounty.balance;
+    let refund = refund_issuer(deps.storage, &env, &bounty, swept)?;
+
+    bounty.status = BountyStatus::Completed;
+    bounty.balance = Uint128::zero();
+
+    BOUNTIES.save(deps.storage, msg.bounty_id, &bounty)?;
+
+    Ok(Response::new()
+        .add_submessages(refund)
+        .add_submessages(transfer_nfts(&bounty.nft_rewards, &bounty.issuer)?)
+        .add_attribute("action", "sweep_merkle")
+        .add_attribute("bounty_id", msg.bounty_id.to_string())
+        .add_attribute("swept", swept))
+}
+
+/// Only the issuer, managers of the bounty's program, or members of its issuer group with
+/// enough weight may manage it. Remote bounties are managed by their controller alone.
+fn ensure_issuer(deps: Deps, bounty: &Bounty, sender: &Addr) -> Result<(), ContractError> {
+    if bounty.controller.is_some() {
+        return Err(ContractError::Unauthorized {});
+    }
+    if sender == bounty.issuer {
+        return Ok(());
+    }
+    if let Some(program_id) = bounty.program_id {
+        if PROGRAMS.load(deps.storage, program_id)?.is_manager(sender) {
+            return Ok(());
+        }
+    }
+    if let Some(group) = &bounty.issuer_group {
+        let member: MemberResponse = deps.querier.query_wasm_smart(
+            &group.contract,
+            &Cw4QueryMsg::Member {
+                addr: sender.to_string(),
+                at_height: None,
+            },
+        )?;
+        if member
+            .weight
+            .is_some_and(|weight| weight >= group.min_weight)
+        {
+            return Ok(());
+        }
+    }
+    Err(ContractError::Unauthorized {})
+}
+
+/// Only the bounty arbiter or the contract admin may challenge payouts
+fn ensure_arbiter(deps: Deps, bounty: &Bounty, info: &MessageInfo) -> Result<(), ContractError> {
+    if bounty.arbiter.as_ref() == Some(&info.sender) {
+        return Ok(());
+    }
+    if CONFIG.load(deps.storage)?.admin == info.sender {
+        return Ok(());
+    }
+    Err(ContractError::Unauthorized {})
+}
+
+/// Pay `amount` to the recipient, return whatever is left to the issuer and mark the bounty completed.
+///
+/// Bounties with vesting terms start a vesting schedule for the recipient instead of paying out.
+fn complete_bounty(
+    deps: DepsMut,
+    env: &Env,
+    bounty_id: u64,
+    mut bounty: Bounty,
+    recipient: Addr,
+    amount: Uint128,
+    action: &str,
+) -> Result<Response, ContractError> {
+    ensure_liquid(&bounty, env)?;
+    let remainder = bounty.balance - amount;
+    let bond = release_bond(deps.storage, env, &mut bounty, false)?;
+
+    bounty.status = BountyStatus::Completed;
+    bounty.set_recipient(Some(recipient.clone()));
+    bounty.balance = Uint128::zero();
+    bounty.attestation = None;
+
+    let mut res = Response::new();
+    if let Some(terms) = bounty.vesting.clone() {
+        let schedule = VestingSchedule {
+            recipient: recipient.clone(),
+            denom: bounty.token_denom.clone(),
+            total: amount,
+            claimed: Uint128::zero(),
+            start: env.block.time,
+            terms,
+        };
+        VESTING.save(deps.storage, bounty_id, &schedule)?;
+    } else if let Some(destination) = &bounty.payout_destination {
+        if !amount.is_zero() {
+            let coin = Coin {
+                denom: bounty.token_denom.clone(),
+                amount,
+            };
+            res = res.add_submessage(ibc_payout(env, &recipient, coin, destination)?);
+        }
+    } else {
+        res = res.add_messages(pay_out(deps.storage, &bounty, &recipient, amount)?);
+    }
+    res = res
+        .add_submessages(refund_issuer(deps.storage, env, &bounty, remainder)?)
+        .add_submessages(bond)
+        .add_submessages(transfer_nfts(&bounty.nft_rewards, &recipient)?);
+
+    if let Some((renewed_id, renewed)) = renew_bounty(deps.storage, env, &mut bounty)? {
+        let hook = BountyHookMsg::Created {
+            bounty_id: renewed_id,
+            issuer: renewed.issuer.clone(),
+        };
+        res = res
+            .add_submessages(prepare_hooks(deps.storage, &renewed, hook)?)
+            .add_attribute("renewed_bounty_id", renewed_id.to_string());
+    }
+
+    BOUNTIES.save(deps.storage, bounty_id, &bounty)?;
+
+    let paid = Coin {
+        denom: bounty.token_denom.clone(),
+        amount,
+    };
+    if let Some(badge_contract) = CONFIG.load(deps.storage)?.badge_contract {
+        res = res.add_message(mint_badge(
+            &badge_contract,
+            bounty_id,
+            &bounty,
+            &recipient,
+            paid.clone(),
+        )?);
+    }
+
+    let hook = BountyHookMsg::Completed {
+        bounty_id,
+        recipient,
+        amount: paid,
+    };
+    res = res.add_submessages(prepare_hooks(deps.storage, &bounty, hook)?);
+
+    Ok(res
+        .add_attribute("action", action)
+        .add_attribute("bounty_id", bounty_id.to_string())
+        .add_attribute("status", "completed"))
+}
+
+/// Open the next cycle of a completed recurring bounty, moving its reserve along.
+///
+/// Program bounties stop recurring once the program cannot fund another cycle.
+fn renew_bounty(
+    storage: &mut dyn Storage,
+    env: &Env,
+    bounty: &mut Bounty,
+) -> StdResult<Option<(u64, Bounty)>> {
+    let Some(recurrence) = bounty.recurrence.as_mut() else {
+        return Ok(None);
+    };
+    if recurrence.cycle >= recurrence.max_cycles {
+        return Ok(None);
+    }
+
+    let mut reserve = std::mem::take(&mut recurrence.reserve);
+    match bounty.program_id {
+        Some(program_id) => {
+            let mut program = PROGRAMS.load(storage, program_id)?;
+            if program.balance < bounty.quantity {
+                return Ok(None);
+            }
+            program.balance -= bounty.quantity;
+            PROGRAMS.save(storage, program_id, &program)?;
+        }
+        None => reserve -= bounty.quantity,
+    }
+
+    let recurrence = Recurrence {
+        cycle: recurrence.cycle + 1,
+        reserve,
+        ..recurrence.clone()
+    };
+    let renewed = Bounty {
+        status: BountyStatus::Open,
+        recipient: recurrence.recipient.clone(),
+        end_height: recurrence.span_height.map(|span| env.block.height + span),
+        end_time: recurrence
+            .span_seconds
+            .map(|span| env.block.time.plus_seconds(span)),
+        balance: bounty.quantity,
+        nonce: 0,
+        attestation: None,
+        payout_destination: None,
+        nft_rewards: vec![],
+        stake: None,
+        assignment: bounty
+            .assignment_period
+            .as_ref()
+            .filter(|_| recurrence.recipient.is_some())
+            .map(|period| period.start(env)),
+        recurrence: Some(recurrence),
+        ..bounty.clone()
+    };
+
+    let id = NEXT_BOUNTY_ID.load(storage)?;
+    BOUNTIES.save(storage, id, &renewed)?;
+    NEXT_BOUNTY_ID.save(storage, &(id + 1))?;
+    // The controller keeps finalizing the bounty under its remote ID, now meaning the new cycle
+    if let Some(controller) = &renewed.controller {
+        REMOTE_BOUNTIES.save(storage, (&controller.channel_id, controller.remote_id), &id)?;
+    }
+
+    Ok(Some((id, renewed)))
+}
+
+/// Mint the achievement badge of a completed bounty to its recipient
+fn mint_badge(
+    badge_contract: &Addr,
+    bounty_id: u64,
+    bounty: &Bounty,
+    recipient: &Addr,
+    amount: Coin,
+) -> StdResult<WasmMsg> {
+    let mint = Cw721ExecuteMsg::Mint {
+        token_id: format!("bounty-{bounty_id}"),
+        owner: recipient.to_string(),
+        token_uri: None,
+        extension: BadgeMetadata {
+            bounty_id,
+            title: bounty.title.clone(),
+            amount,
+            tags: bounty.tags.clone(),
+        },
+    };
+    Ok(WasmMsg::Execute {
+        contract_addr: badge_contract.to_string(),
+        msg: to_json_binary(&mint)?,
+        funds: vec![],
+    })
+}
+
+/// Return the remaining balance to the issuer and mark the bounty expired
+fn refund_bounty(
+    deps: DepsMut,
+    env: &Env,
+    bounty_id: u64,
+    mut bounty: Bounty,
+    action: &str,
+) -> Result<Response, ContractError> {
+    ensure_liquid(&bounty, env)?;
+    // Expiry ends the recurrence, returning the reserve of later cycles as well
+    let reserve = bounty
+        .recurrence
+        .as_mut()
+        .map(|r| std::mem::take(&mut r.reserve))
+        .unwrap_or_default();
+    let refund = refund_issuer(deps.storage, env, &bounty, bounty.balance + reserve)?;
+    // The assigned hunter forfeits their bond only when they missed a deadline without
+    // submitting any work
+    let expired = check_expired(&bounty, env);
+    let slash = match &bounty.assignment {
+        Some(assignment) => assignment.lapsed(env) || (assignment.submission.is_none() && expired),
+        None => expired,
+    };
+    let bond = release_bond(deps.storage, env, &mut bounty, slash)?;
+
+    bounty.status = BountyStatus::Expired;
+    bounty.balance = Uint128::zero();
+    bounty.attestation = None;
+
+    BOUNTIES.save(deps.storage, bounty_id, &bounty)?;
+
+    let hook = BountyHookMsg::Expired { bounty_id };
+    Ok(Response::new()
+        .add_submessages(refund)
+        .add_submessages(bond)
+        .add_submessages(transfer_nfts(&bounty.nft_rewards, &bounty.issuer)?)
+        .add_submessages(prepare_hooks(deps.storage, &bounty, hook)?)
+        .add_attribute("action", action)
+        .add_attribute("bounty_id", bounty_id.to_string())
+        .add_attribute("status", "expired"))
+}
+
+/// Top up an open bounty, raising its payout above the promised quantity
+pub fn execute_fund_bounty(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: FundBountyMsg,
+) -> Result<Response, ContractError> {
+    let mut bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
+
+    ensure_issuer(deps.as_ref(), &bounty, &info.sender)?;
+
+    ensure_bounty_open(&bounty)?;
+    ensure_liquid(&bounty, &env)?;
+
+    let amount = validate_funds(&info.funds, &bounty.token_denom, Uint128::zero())?;
+    if amount.is_zero() {
+        return Err(ContractError::InvalidFunds {});
+    }
+
+    bounty.balance += amount;
+    BOUNTIES.save(deps.storage, msg.bounty_id, &bounty)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "fund_bounty")
+        .add_attribute("bounty_id", msg.bounty_id.to_string())
+        .add_attribute("amount", amount))
+}
+
+/// Return the assigned hunter's bond to them, or slash it to the issuer
+fn release_bond(
+    storage: &mut dyn Storage,
+    env: &Env,
+    bounty: &mut Bounty,
+    slash: bool,
+) -> StdResult<Option<SubMsg>> {
+    let bond = std::mem::take(&mut bounty.held_bond);
+    match bounty.recipient.clone() {
+        Some(hunter) if !slash => Ok(pay_out(storage, bounty, &hunter, bond)?.map(SubMsg::new)),
+        _ => refund_issuer(storage, env, bounty, bond),
+    }
+}
+
+/// Send `amount` of the bounty denom to `to`, or credit it to their claimable balance for
+/// pull-payment bounties
+fn pay_out(
+    storage: &mut dyn Storage,
+    bounty: &Bounty,
+    to: &Addr,
+    amount: Uint128,
+) -> StdResult<Option<BankMsg>> {
+    if amount.is_zero() {
+        return Ok(None);
+    }
+    if bounty.pull_payments {
+        credit_claimable(storage, to, &bounty.token_denom, amount)?;
+        return Ok(None);
+    }
+    Ok(Some(BankMsg::Send {
+        to_address: to.to_string(),
+        amount: vec![Coin {
+            denom: bounty.token_denom.clone(),
+            amount,
+        }],
+    }))
+}
+
+/// Transfer the escrowed NFT prizes of a bounty to `to`, ignoring transfers that fail
+fn transfer_nfts(rewards: &[NftReward], to: &Addr) -> StdResult<Vec<SubMsg>> {
+    rewards
+        .iter()
+        .map(|reward| {
+            let transfer = Cw721ExecuteMsg::<Empty>::TransferNft {
+                recipient: to.to_string(),
+                token_id: reward.token_id.clone(),
+            };
+            let msg = WasmMsg::Execute {
+                contract_addr: reward.contract.to_string(),
+                msg: to_json_binary(&transfer)?,
+                funds: vec![],
+            };
+            Ok(SubMsg::reply_on_error(msg, NFT_TRANSFER_REPLY_ID))
+        })
+        .collect()
+}
+
+/// Return `amount` to the issuer, over IBC to the controller's refund account for remote bounties
+/// and to the program budget for program bounties
+fn refund_issuer(
+    storage: &mut dyn Storage,
+    env: &Env,
+    bounty: &Bounty,
+    amount: Uint128,
+) -> StdResult<Option<SubMsg>> {
+    if let Some(program_id) = bounty.program_id {
+        let mut program = PROGRAMS.load(storage, program_id)?;
+        program.balance += amount;
+        PROGRAMS.save(storage, program_id, &program)?;
+        return Ok(None);
+    }
+    let refund_to = bounty
+        .controller
+        .as_ref()
+        .and_then(|c| c.refund_to.as_ref());
+    match refund_to {
+        Some(destination) if !amount.is_zero() => {
+            let coin = Coin {
+                denom: bounty.token_denom.clone(),
+                amount,
+            };
+            ibc_payout(env, &bounty.issuer, coin, destination).map(Some)
+        }
+        _ => Ok(pay_out(storage, bounty, &bounty.issuer, amount)?.map(SubMsg::new)),
+    }
+}
+
+/// Create a program whose budget is the sent funds
+pub fn execute_create_program(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: CreateProgramMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if msg.title.trim().is_empty() {
+        return Err(ContractError::EmptyTitle {});
+    }
+    if msg.title.len() > config.max_title_length as usize {
+        return Err(ContractError::TitleTooLong {
+            max: config.max_title_length,
+        });
+    }
+    let balance = validate_funds(&info.funds, &msg.token_denom, Uint128::zero())?;
+
+    let id = NEXT_PROGRAM_ID.load(deps.storage)?;
+    let program = Program {
+        title: msg.title,
+        owner: info.sender.clone(),
+        reviewers: msg
+            .reviewers
+            .iter()
+            .map(|r| deps.api.addr_validate(r))
+            .collect::<StdResult<_>>()?,
+        token_denom: msg.token_denom,
+        balance,
+    };
+    PROGRAMS.save(deps.storage, id, &program)?;
+    NEXT_PROGRAM_ID.save(deps.storage, &(id + 1))?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_program")
+        .add_attribute("program_id", id.to_string())
+        .add_attribute("owner", info.sender)
+        .add_attribute("balance", balance))
+}
+
+/// Add the sent funds to a program's budget
+pub fn execute_fund_program(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: FundProgramMsg,
+) -> Result<Response, ContractError> {
+    let mut program = PROGRAMS.load(deps.storage, msg.program_id)?;
+    let amount = validate_funds(&info.funds, &program.token_denom, Uint128::zero())?;
+    if amount.is_zero() {
+        return Err(ContractError::InvalidFunds {});
+    }
+
+    program.balance += amount;
+    PROGRAMS.save(deps.storage, msg.program_id, &program)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "fund_program")
+        .add_attribute("program_id", msg.program_id.to_string())
+        .add_attribute("amount", amount))
+}
+
+/// Withdraw budget of a program not committed to any child bounty
+pub fn execute_withdraw_program(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: WithdrawProgramMsg,
+) -> Result<Response, ContractError> {
+    let mut program = PROGRAMS.load(deps.storage, msg.program_id)?;
+
+    if info.sender != program.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+    if msg.amount.is_zero() || msg.amount > program.balance {
+        return Err(ContractError::InsufficientFunds {});
+    }
+
+    program.balance -= msg.amount;
+    PROGRAMS.save(deps.storage, msg.program_id, &program)?;
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: program.owner.to_string(),
+            amount: vec![Coin {
+                denom: program.token_denom,
+                amount: msg.amount,
+            }],
+        })
+        .add_attribute("action", "withdraw_program")
+        .add_attribute("program_id", msg.program_id.to_string())
+        .add_attribute("amount", msg.amount))
+}
+
+/// Delegate an open bounty's balance to the configured validator until its payout approaches
+pub fn execute_stake_bounty(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: StakeBountyMsg,
+) -> Result<Response, ContractError> {
+    let mut bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
+    let staking = CONFIG
+        .load(deps.storage)?
+        .staking
+        .ok_or(ContractError::StakingDisabled {})?;
+
+    ensure_issuer(deps.as_ref(), &bounty, &info.sender)?;
+    ensure_bounty_open(&bounty)?;
+    ensure_single_payout(&bounty)?;
+    if bounty.stake.is_some() {
+        return Err(ContractError::AlreadyStaked {});
+    }
+    if bounty.balance.is_zero() || bounty.token_denom != deps.querier.query_bonded_denom()? {
+        return Err(ContractError::NotStakeable {});
+    }
+
+    // Rewards earned so far belong to the bounties already staked
+    let withdraw = accrue_yield(deps.storage, &deps.querier, &env, &staking)?;
+    TOTAL_STAKED.update(deps.storage, |total| -> StdResult<_> {
+        Ok(total + bounty.balance)
+    })?;
+    bounty.stake = Some(Stake {
+        amount: bounty.balance,
+        index: YIELD_INDEX.load(deps.storage)?,
+        unbonding: Uint128::zero(),
+        unbonding_until: None,
+    });
+    BOUNTIES.save(deps.storage, msg.bounty_id, &bounty)?;
+
+    let delegate = StakingMsg::Delegate {
+        validator: staking.validator,
+        amount: Coin {
+            denom: bounty.token_denom,
+            amount: bounty.balance,
+        },
+    };
+    Ok(Response::new()
+        .add_messages(withdraw)
+        .add_message(delegate)
+        .add_attribute("action", "stake_bounty")
+        .add_attribute("bounty_id", msg.bounty_id.to_string())
+        .add_attribute("amount", bounty.balance))
+}
+
+/// Undelegate a bounty's balance, paying out its yield.
+///
+/// The issuer may unstake at any time, anyone else once the payout approaches.
+pub fn execute_unstake_bounty(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: UnstakeBountyMsg,
+) -> Result<Response, ContractError> {
+    let mut bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
+    let staking = CONFIG
+        .load(deps.storage)?
+        .staking
+        .ok_or(ContractError::StakingDisabled {})?;
+
+    let staked = bounty
+        .stake
+        .as_ref()
+        .map(|stake| stake.amount)
+        .unwrap_or_default();
+    if staked.is_zero() {
+        return Err(ContractError::NotStaked {});
+    }
+    if !payout_approaching(&bounty, &env, &staking) {
+        ensure_issuer(deps.as_ref(), &bounty, &info.sender)?;
+    }
+
+    let withdraw = accrue_yield(deps.storage, &deps.querier, &env, &staking)?;
+    let earned = settle_yield(deps.storage, &env, &mut bounty, &staking)?;
+
+    // Slashing shrinks the pool's delegation, and every staked bounty in proportion
+    let total = TOTAL_STAKED.load(deps.storage)?;
+    let delegated = deps
+        .querier
+        .query_delegation(&env.contract.address, &staking.validator)?
+        .map(|delegation| delegation.amount.amount)
+        .unwrap_or_default();
+    let amount = staked.multiply_ratio(delegated.min(total), total);
+    TOTAL_STAKED.save(deps.storage, &(total - staked))?;
+    bounty.balance -= staked - amount;
+    bounty.stake = Some(Stake {
+        amount: Uint128::zero(),
+        index: Decimal::zero(),
+        unbonding: amount,
+        unbonding_until: Some(env.block.time.plus_seconds(staking.unbonding_period)),
+    });
+    BOUNTIES.save(deps.storage, msg.bounty_id, &bounty)?;
+
+    let undelegate = StakingMsg::Undelegate {
+        validator: staking.validator,
+        amount: Coin {
+            denom: bounty.token_denom,
+            amount,
+        },
+    };
+    Ok(Response::new()
+        .add_messages(withdraw)
+        .add_message(undelegate)
+        .add_submessages(earned)
+        .add_attribute("action", "unstake_bounty")
+        .add_attribute("bounty_id", msg.bounty_id.to_string())
+        .add_attribute("amount", amount))
+}
+
+/// Pay out the yield a staked bounty earned so far
+pub fn execute_harvest_yield(
+    deps: DepsMut,
+    env: Env,
+    msg: HarvestYieldMsg,
+) -> Result<Response, ContractError> {
+    let mut bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
+    let staking = CONFIG
+        .load(deps.storage)?
+        .staking
+        .ok_or(ContractError::StakingDisabled {})?;
+
+    if bounty
+        .stake
+        .as_ref()
+        .is_none_or(|stake| stake.amount.is_zero())
+    {
+        return Err(ContractError::NotStaked {});
+    }
+
+    let withdraw = accrue_yield(deps.storage, &deps.querier, &env, &staking)?;
+    let earned = settle_yield(deps.storage, &env, &mut bounty, &staking)?;
+    BOUNTIES.save(deps.storage, msg.bounty_id, &bounty)?;
+
+    Ok(Response::new()
+        .add_messages(withdraw)
+        .add_submessages(earned)
+        .add_attribute("action", "harvest_yield")
+        .add_attribute("bounty_id", msg.bounty_id.to_string()))
+}
+
+/// Whether work was submitted or attested, or the deadline is within the unbond window
+fn payout_approaching(bounty: &Bounty, env: &Env, staking: &StakingConfig) -> bool {
+    bounty
+        .assignment
+        .as_ref()
+        .is_some_and(|assignment| assignment.submission.is_some())
+        || bounty.attestation.is_some()
+        || bounty
+            .end_height
+            .is_some_and(|end| end <= env.block.height + staking.unbond_window_blocks)
+        || bounty
+            .end_time
+            .is_some_and(|end| end <= env.block.time.plus_seconds(staking.unbond_window))
+        || check_expired(bounty, env)
+}
+
+/// Credit the rewards pending on the pool's delegation to `YIELD_INDEX`, withdrawing them
+fn accrue_yield(
+    storage: &mut dyn Storage,
+    querier: &QuerierWrapper,
+    env: &Env,
+    staking: &StakingConfig,
+) -> StdResult<Option<DistributionMsg>> {
+    let total = TOTAL_STAKED.load(storage)?;
+    if total.is_zero() {
+        return Ok(None);
+    }
+    let Some(delegation) = querier.query_delegation(&env.contract.address, &staking.validator)?
+    else {
+        return Ok(None);
+    };
+    let rewards: Uint128 = delegation
+        .accumulated_rewards
+        .iter()
+        .filter(|reward| reward.denom == delegation.amount.denom)
+        .map(|reward| reward.amount)
+        .sum();
+    YIELD_INDEX.update(storage, |index| -> StdResult<_> {
+        Ok(index + Decimal::from_ratio(rewards, total))
+    })?;
+    Ok(Some(DistributionMsg::WithdrawDelegatorReward {
+        validator: staking.validator.clone(),
+    }))
+}
+
+/// Pay the yield a bounty's delegation earned since it was last settled to the configured
+/// recipient, falling back to the issuer while no hunter is assigned
+fn settle_yield(
+    storage: &mut dyn Storage,
+    env: &Env,
+    bounty: &mut Bounty,
+    staking: &StakingConfig,
+) -> StdResult<Option<SubMsg>> {
+    let Some(stake) = bounty.stake.as_mut() else {
+        return Ok(None);
+    };
+    let index = YIELD_INDEX.load(storage)?;
+    let earned = stake.amount.mul_floor(index - stake.index);
+    stake.index = index;
+
+    match (&staking.yield_recipient, bounty.recipient.clone()) {
+        (YieldRecipient::Treasury(treasury), _) => {
+            Ok(pay_out(storage, bounty, treasury, earned)?.map(SubMsg::new))
+        }
+        (YieldRecipient::Hunter, Some(hunter)) => {
+            Ok(pay_out(storage, bounty, &hunter, earned)?.map(SubMsg::new))
+        }
+        _ => refund_issuer(storage, env, bounty, earned),
+    }
+}
+
+/// Delegated funds, and undelegated ones until their unbonding completes, cannot be paid out
+/// or refunded
+fn ensure_liquid(bounty: &Bounty, env: &Env) -> Result<(), ContractError> {
+    if bounty
+        .stake
+        .as_ref()
+        .is_some_and(|stake| !stake.liquid(env))
+    {
+        return Err(ContractError::FundsStaked {});
+    }
+    Ok(())
+}
+
+/// Withdraw the part of an open bounty's balance that exceeds its promised quantity
+pub fn execute_withdraw_excess(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: WithdrawExcessMsg,
+) -> Result<Response, ContractError> {
+    let mut bounty = BOUNTIES.load(deps.storage, msg.bounty_id)?;
+
+    ensure_issuer(deps.as_ref(), &bounty, &info.sender)?;
+
+    ensure_bounty_open(&bounty)?;
+    ensure_liquid(&bounty, &env)?;
+
+    let available = bounty.balance.saturating_sub(bounty.quantity);
+    if msg.amount.is_zero() || msg.amount > available {
+        return Err(ContractError::InvalidExcessWithdrawal { available });
+    }
+
+    bounty.balance -= msg.amount;
+    let withdrawal = refund_issuer(deps.storage, &env, &bounty, msg.amount)?;
+
+    BOUNTIES.save(deps.storage, msg.bounty_id, &bounty)?;
+
+    Ok(Response::new()
+        .add_submessages(withdrawal)
+        .add_attribute("action", "withdraw_excess")
+        .add_attribute("bounty_id", msg.bounty_id.to_string())
+        .add_attribute("amount", msg.amount.to_string()))
+}
+
+/// Claim the vested, not yet claimed part of a completed bounty's payout
+pub fn execute_claim_vested(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ClaimVestedMsg,
+) -> Result<Response, ContractError> {
+    let mut schedule = VESTING.load(deps.storage, msg.bounty_id)?;
+
+    if info.sender != schedule.recipient {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let amount = schedule.claimable(env.block.time);
+    if amount.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+    schedule.claimed += amount;
+
+    VESTING.save(deps.storage, msg.bounty_id, &schedule)?;
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: schedule.recipient.to_string(),
+            amount: vec![Coin {
+                denom: schedule.denom,
+                amount,
+            }],
+        })
+        .add_attribute("action", "claim_vested")
+        .add_attribute("bounty_id", msg.bounty_id.to_string())
+        .add_attribute("amount", amount.to_string()))
+}
+
+/// Withdraw the sender's claimable balances, optionally limited to some denoms
+pub fn execute_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ClaimMsg,
+) -> Result<Response, ContractError> {
+    if let Some(destination) = &msg.ibc {
+        validate_ibc_destination(destination)?;
+    }
+
+    let balances = CLAIMABLE
+        .prefix(&info.sender)
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|item| match (item, &msg.denoms) {
+            (Ok((denom, _)), Some(denoms)) => denoms.contains(denom),
+            _ => true,
+        })
+        .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    if balances.is_empty() {
+        return Err(ContractError::NothingToClaim {});
+    }
+    for coin in &balances {
+        CLAIMABLE.remove(deps.storage, (&info.sender, &coin.denom));
+    }
+
+    let res = match &msg.ibc {
+        Some(destination) => Response::new().add_submessages(
+            balances
+                .into_iter()
+                .map(|coin| ibc_payout(&env, &info.sender, coin, destination))
+                .collect::<StdResult<Vec<_>>>()?,
+        ),
+        None => Response::new().add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: balances,
+        }),
+    };
+
+    Ok(res
+        .add_attribute("action", "claim")
+        .add_attribute("claimant", info.sender))
+}
+
+/// Withdraw a claimable balance swapped to `ask_denom` through the configured router.
+///
+/// The swap output is forwarded to the claimant in the reply, which fails the whole claim when
+/// it falls short of `min_receive`.
+pub fn execute_claim_and_swap(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ClaimAndSwapMsg,
+) -> Result<Response, ContractError> {
+    let router = CONFIG
+        .load(deps.storage)?
+        .swap_router
+        .ok_or(ContractError::SwapRouterNotSet {})?;
+    if msg.denom == msg.ask_denom {
+        return Err(ContractError::InvalidSwap {});
+    }
+
+    let key = (&info.sender, msg.denom.as_str());
+    let amount = CLAIMABLE
+        .may_load(deps.storage, key)?
+        .ok_or(ContractError::NothingToClaim {})?;
+    CLAIMABLE.remove(deps.storage, key);
+
+    let pending = PendingSwap {
+        recipient: info.sender.clone(),
+        balance_before: deps
+            .querier
+            .query_balance(&env.contract.address, &msg.ask_denom)?
+            .amount,
+        ask_denom: msg.ask_denom.clone(),
+        min_receive: msg.min_receive,
+    };
+    let swap = WasmMsg::Execute {
+        contract_addr: router.to_string(),
+        msg: to_json_binary(&RouterExecuteMsg::Swap {
+            ask_denom: msg.ask_denom,
+            minimum_receive: Some(msg.min_receive),
+        })?,
+        funds: vec![Coin {
+            denom: msg.denom,
+            amount,
+        }],
+    };
+
+    Ok(Response::new()
+        .add_submessage(
+            SubMsg::reply_on_success(swap, SWAP_REPLY_ID).with_payload(to_json_binary(&pending)?),
+        )
+        .add_attribute("action", "claim_and_swap")
+        .add_attribute("claimant", info.sender)
+        .add_attribute("amount", amount))
+}
+
+/// Forward the swap output to the claimant once it meets their minimum
+fn handle_swap_reply(deps: Deps, env: &Env, reply: Reply) -> Result<Response, ContractError> {
+    let pending: PendingSwap = from_json(&reply.payload)?;
+    let balance = deps
+        .querier
+        .query_balance(&env.contract.address, &pending.ask_denom)?
+        .amount;
+    let received = balance.saturating_sub(pending.balance_before);
+    if received < pending.min_receive {
+        return Err(ContractError::SwapBelowMinimum {
+            received,
+            min_receive: pending.min_receive,
+        });
+    }
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: pending.recipient.to_string(),
+            amount: vec![Coin {
+                denom: pending.ask_denom,
+                amount: received,
+            }],
+        })
+        .add_attribute("action", "swap_reply")
+        .add_attribute("recipient", pending.recipient)
+        .add_attribute("received", received))
+}
+
+/// Register a contract to be called back on every bounty event
+pub fn execute_add_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: HookMsg,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.as_ref(), &info)?;
+
+    let hook = deps.api.addr_validate(&msg.address)?;
+    let mut hooks = HOOKS.may_load(deps.storage)?.unwrap_or_default();
+    if hooks.contains(&hook) {
+        return Err(ContractError::HookAlreadyRegistered {});
+    }
+    hooks.push(hook);
+    HOOKS.save(deps.storage, &hooks)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_hook")
+        .add_attribute("hook", msg.address))
+}
+
+/// Stop calling back a registered hook contract
+pub fn execute_remove_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: HookMsg,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.as_ref(), &info)?;
+
+    let hook = deps.api.addr_validate(&msg.address)?;
+    let mut hooks = HOOKS.may_load(deps.storage)?.unwrap_or_default();
+    if !hooks.contains(&hook) {
+        return Err(ContractError::HookNotRegistered {});
+    }
+    hooks.retain(|h| *h != hook);
+    HOOKS.save(deps.storage, &hooks)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_hook")
+        .add_attribute("hook", msg.address))
+}
+
+/// Only the contract admin may manage contract-wide settings
+fn ensure_admin(deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
+    if CONFIG.load(deps.storage)?.admin != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(())
+}
+
+/// Update the contract configuration
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: UpdateConfigMsg,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(admin) = msg.admin {
+        config.admin = deps.api.addr_validate(&admin)?;
+    }
+    if let Some(max_title_length) = msg.max_title_length {
+        config.max_title_length = max_title_length;
+    }
+    if let Some(max_description_length) = msg.max_description_length {
+        config.max_description_length = max_description_length;
+    }
+    if let Some(badge_contract) = msg.badge_contract {
+        config.badge_contract = Some(deps.api.addr_validate(&badge_contract)?);
+    }
+    if let Some(staking) = msg.staking {
+        config.staking = Some(validate_staking_config(deps.as_ref(), staking)?);
+    }
+    if let Some(price_oracle) = msg.price_oracle {
+        config.price_oracle = Some(deps.api.addr_validate(&price_oracle)?);
+    }
+    if let Some(swap_router) = msg.swap_router {
+        config.swap_router = Some(deps.api.addr_validate(&swap_router)?);
+    }
+    if let Some(nft_contracts) = msg.nft_contracts {
+        config.nft_contracts = nft_contracts
+            .iter()
+            .map(|addr| deps.api.addr_validate(addr))
+            .collect::<StdResult<_>>()?;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+/// The validator must exist on this chain
+fn validate_staking_config(
+    deps: Deps,
+    msg: StakingConfigMsg,
+) -> Result<StakingConfig, ContractError> {
+    if deps.querier.query_validator(&msg.validator)?.is_none() {
+        return Err(ContractError::InvalidValidator {});
+    }
+    let yield_recipient = match msg.yield_recipient {
+        YieldRecipientMsg::Issuer => YieldRecipient::Issuer,
+        YieldRecipientMsg::Hunter => YieldRecipient::Hunter,
+        YieldRecipientMsg::Treasury(treasury) => {
+            YieldRecipient::Treasury(deps.api.addr_validate(&treasury)?)
+        }
+    };
+    Ok(StakingConfig {
+        validator: msg.validator,
+        yield_recipient,
+        unbond_window: msg.unbond_window,
+        unbond_window_blocks: msg.unbond_window_blocks,
+        unbonding_period: msg.unbonding_period,
+    })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        IBC_TRANSFER_REPLY_ID => {
+            handle_transfer_reply(deps, msg)?;
+            Ok(Response::new().add_attribute("action", "ibc_transfer_reply"))
+        }
+        SWAP_REPLY_ID => handle_swap_reply(deps.as_ref(), &env, msg),
+        // A failed hook is reverted on its own and doesn't affect the bounty
+        HOOK_REPLY_ID => Ok(Response::new()
+            .add_attribute("action", "hook_failed")
+            .add_attribute("error", msg.result.unwrap_err())),
+        // The prize stays escrowed rather than blocking the payout of the rest of the bounty
+        NFT_TRANSFER_REPLY_ID => Ok(Response::new()
+            .add_attribute("action", "nft_transfer_failed")
+            .add_attribute("error", msg.result.unwrap_err())),
+        id => Err(StdError::generic_err(format!("Unknown reply id: {id}")).into()),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::GetBounty(msg) => to_json_binary(&query_bounty(deps, msg.bounty_id)?),
+        QueryMsg::ListBounties {} => to_json_binary(&query_all_bounties(deps)?),
+        QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Hooks {} => to_json_binary(&HooksResponse {
+            hooks: HOOKS.may_load(deps.storage)?.unwrap_or_default(),
+        }),
+        QueryMsg::Vesting(msg) => to_json_binary(&query_vesting(deps, env, msg.bounty_id)?),
+        QueryMsg::Claimable { address } => to_json_binary(&query_claimable(deps, address)?),
+        QueryMsg::Applications(msg) => to_json_binary(&query_applications(deps, msg.bounty_id)?),
+        QueryMsg::Votes(msg) => to_json_binary(&query_votes(deps, msg.bounty_id)?),
+        QueryMsg::Entries(msg) => to_json_binary(&query_entries(deps, msg.bounty_id)?),
+        QueryMsg::Reports(msg) => to_json_binary(&query_reports(deps, msg.bounty_id)?),
+        QueryMsg::Program { program_id } => {
+            to_json_binary(&PROGRAMS.load(deps.storage, program_id)?)
+        }
+    }
+}
+
+pub fn query_bounty(deps: Deps, bounty_id: u64) -> StdResult<Bounty> {
+    let bounty = BOUNTIES.load(deps.storage, bounty_id)?;
+    Ok(bounty)
+}
+
+pub fn query_all_bounties(deps: Deps) -> StdResult<Vec<Bounty>> {
+    BOUNTIES
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .map(|item| item.map(|(_, bounty)| bounty))
+        .collect()
+}
+
+pub fn query_applications(deps: Deps, bounty_id: u64) -> StdResult<ApplicationsResponse> {
+    let applications = APPLICATIONS
+        .prefix(bounty_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    Ok(ApplicationsResponse { applications })
+}
+
+pub fn query_votes(deps: Deps, bounty_id: u64) -> StdResult<VotesResponse> {
+    let threshold = BOUNTIES.load(deps.storage, bounty_id)?.threshold;
+    let votes: Vec<(Addr, bool)> = VOTES
+        .prefix(bounty_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    let approvals = votes.iter().filter(|(_, approve)| *approve).count() as u32;
+    Ok(VotesResponse {
+        threshold,
+        approvals,
+        rejections: votes.len() as u32 - approvals,
+        votes,
+    })
+}
+
+pub fn query_entries(deps: Deps, bounty_id: u64) -> StdResult<EntriesResponse> {
+    let entries = ENTRIES
+        .prefix(bounty_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    Ok(EntriesResponse { entries })
+}
+
+pub fn query_reports(deps: Deps, bounty_id: u64) -> StdResult<ReportsResponse> {
+    let reports = REPORTS
+        .prefix(bounty_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    Ok(ReportsResponse { reports })
+}
+
+pub fn query_claimable(deps: Deps, address: String) -> StdResult<ClaimableResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let balances = CLAIMABLE
+        .prefix(&address)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+        .collect::<StdResult<_>>()?;
+    Ok(ClaimableResponse { balances })
+}
+
+pub fn query_vesting(deps: Deps, env: Env, bounty_id: u64) -> StdResult<VestingResponse> {
+    let schedule = VESTING.load(deps.storage, bounty_id)?;
+    Ok(VestingResponse {
+        vested: schedule.vested(env.block.time),
+        claimable: schedule.claimable(env.block.time),
+        schedule,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hooks::{BountyHookExecuteMsg, HOOK_GAS_LIMIT};
+    use crate::merkle::hash_pair;
+    use crate::msg::{
+        ContestMsg, CreateBountyMsg, ExecuteMsg, InstantiateMsg, IssuerGroupMsg, QueryMsg,
+        RecurrenceMsg,
+    };
+    use crate::state::{
+        ApproverKey, AssignmentPeriod, BountyStatus, IbcDestination, Severity, SeverityTiers,
+        SignatureScheme, VestingTerms, BOUNTIES, CONTEST_SETTLE_BLOCKS, NEXT_BOUNTY_ID,
+    };
+    use crate::validation::MAX_HOOKS;
+    use cosmwasm_std::testing::{
+        message_info, mock_dependencies, mock_env, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{
+        attr, coin, coins, from_json, ContractResult, CosmosMsg, FullDelegation, IbcMsg, OwnedDeps,
+        SubMsg, SubMsgResponse, SubMsgResult, SystemResult, Uint128, Validator, WasmQuery,
+    };
+
+    type TestDeps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+    /// Instantiate the contract with the default bounds
+    fn proper_instantiate() -> (TestDeps, Env) {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+
+        let msg = InstantiateMsg {
+            start_bounty_id: 1,
+            admin: None,
+            max_title_length: None,
+            max_description_length: None,
+            badge_contract: None,
+            staking: None,
+            price_oracle: None,
+            swap_router: None,
+            nft_contracts: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        (deps, env)
+    }
+
+    /// A valid bounty paying `quantity` tokens
+    fn bounty_msg(env: &Env, quantity: u128) -> CreateBountyMsg {
+        CreateBountyMsg {
+            title: "Fix a bug".to_string(),
+            description: "Fix a critical bug in the system".to_string(),
+            recipient: None,
+            end_height: Some(env.block.height + 100),
+            end_time: None,
+            token_denom: "token".to_string(),
+            quantity: Uint128::new(quantity),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_instantiate() {
+        let (deps, _env) = proper_instantiate();
+
+        let id = NEXT_BOUNTY_ID.load(deps.as_ref().storage).unwrap();
+        assert_eq!(id, 1u64);
+        let version = cw2::get_contract_version(deps.as_ref().storage).unwrap();
+        assert_eq!(version.contract, CONTRACT_NAME);
+
+        let mut deps = mock_dependencies();
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+        let msg = InstantiateMsg {
+            start_bounty_id: 100,
+            admin: None,
+            max_title_length: None,
+            max_description_length: None,
+            badge_contract: None,
+            staking: None,
+            price_oracle: None,
+            swap_router: None,
+            nft_contracts: None,
+        };
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let id = NEXT_BOUNTY_ID.load(deps.as_ref().storage).unwrap();
+        assert_eq!(id, 100u64);
+    }
+
+    #[test]
+    fn test_create_bounty() {
+        let (mut deps, env) = proper_instantiate();
+        let creator = deps.api.addr_make("creator");
+        let developer = deps.api.addr_make("developer");
+
+        let msg = CreateBountyMsg {
+            recipient: Some(developer.to_string()),
+            ..bounty_msg(&env, 500)
+        };
+        let info = message_info(&creator, &coins(500, "token"));
+        let res = execute(
+            deps.as_mut(),
+            env,
+            info,
+            ExecuteMsg::CreateBounty(Box::new(msg)),
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&attr("bounty_id", "1")));
+
+        let bounty = BOUNTIES.load(deps.as_ref().storage, 1).unwrap();
+        assert_eq!(bounty.title, "Fix a bug");
+        assert_eq!(bounty.description, "Fix a critical bug in the system");
+        assert_eq!(bounty.recipient, Some(developer));
+        assert_eq!(bounty.balance, Uint128::new(500));
+        assert_eq!(bounty.status, BountyStatus::Open);
+    }
+
+    #[test]
+    fn test_finalize_bounty_success() {
+        let (mut deps, env) = proper_instantiate();
+        let creator = deps.api.addr_make("creator");
+        let developer = deps.api.addr_make("developer");
+
+        let msg = CreateBountyMsg {
+            recipient: Some(developer.to_string()),
+            ..bounty_msg(&env, 500)
+        };
+        let info = message_info(&creator, &coins(500, "token"));
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ExecuteMsg::CreateBounty(Box::new(msg)),
+        )
+        .unwrap();
+
+        let finalize = ExecuteMsg::FinalizeBounty(FinalizeBountyMsg {
+            bounty_id: 1,
+            success: true,
+        });
+        let info = message_info(&creator, &[]);
+        let res = execute(deps.as_mut(), env, info, finalize).unwrap();
+        assert_eq!(
+            res.messages[0],
+            SubMsg::new(BankMsg::Send {
+                to_address: developer.to_string(),
+                amount: coins(500, "token"),
+            })
+        );
+
+        let bounty = BOUNTIES.load(deps.as_ref().storage, 1).unwrap();
+        assert_eq!(bounty.status, BountyStatus::Completed);
+    }
+
+    #[test]
+    fn test_expire_bounty() {
+        let (mut deps, mut env) = proper_instantiate();
+        let creator = deps.api.addr_make("creator");
+
+        let msg = CreateBountyMsg {
+            end_height: Some(env.block.height + 1),
+            ..bounty_msg(&env, 300)
+        };
+        let info = message_info(&creator, &coins(300, "token"));
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ExecuteMsg::CreateBounty(Box::new(msg)),
+        )
+        .unwrap();
+
+        env.block.height += 10;
+        let expire = ExecuteMsg::ExpireBounty(ExpireBountyMsg { bounty_id: 1 });
+        let info = message_info(&creator, &[]);
+        let res = execute(deps.as_mut(), env, info, expire).unwrap();
+        assert_eq!(
+            res.messages[0],
+            SubMsg::new(BankMsg::Send {
+                to_address: creator.to_string(),
+                amount: coins(300, "token"),
+            })
+        );
+
+        let bounty = BOUNTIES.load(deps.as_ref().storage, 1).unwrap();
+        assert_eq!(bounty.status, BountyStatus::Expired);
+    }
+
+    #[test]
+    fn test_query_all_bounties() {
+        let (mut deps, env) = proper_instantiate();
+        let creator = deps.api.addr_make("creator");
+
+        for i in 1..=3 {
+            let msg = CreateBountyMsg {
+                title: format!("Bounty {}", i),
+                ..bounty_msg(&env, 100 * i)
+            };
+            let info = message_info(&creator, &coins(100 * i, "token"));
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                info,
+                ExecuteMsg::CreateBounty(Box::new(msg)),
+            )
+            .unwrap();
+        }
+
+        let res = query(deps.as_ref(), env, QueryMsg::ListBounties {}).unwrap();
+        let bounties: Vec<Bounty> = from_json(res).unwrap();
+        assert_eq!(bounties.len(), 3);
+        assert_eq!(bounties[0].title, "Bounty 1");
+        assert_eq!(bounties[1].title, "Bounty 2");
+        assert_eq!(bounties[2].title, "Bounty 3");
+    }
+
+    #[test]
+    fn test_create_bounty_validation() {
+        let (mut deps, env) = proper_instantiate();
+        let info = message_info(&deps.api.addr_make("creator"), &coins(500, "token"));
+
+        let mut msg = bounty_msg(&env, 500);
+        msg.title = "  ".to_string();
+        let err = execute_create_bounty(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert!(matches!(err, ContractError::EmptyTitle {}));
+
+        let mut msg = bounty_msg(&env, 500);
+        msg.title = "a".repeat(DEFAULT_MAX_TITLE_LENGTH as usize + 1);
+        let err = execute_create_bounty(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert!(matches!(err, ContractError::TitleTooLong { .. }));
+
+        let mut msg = bounty_msg(&env, 500);
+        msg.end_height = None;
+        let err = execute_create_bounty(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert!(matches!(err, ContractError::MissingDeadline {}));
+
+        let mut msg = bounty_msg(&env, 500);
+        msg.end_height = Some(env.block.height);
+        let err = execute_create_bounty(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert!(matches!(err, ContractError::DeadlineInPast {}));
+
+        let msg = bounty_msg(&env, 0);
+        let err = execute_create_bounty(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+        assert!(matches!(err, ContractError::ZeroQuantity {}));
+    }
+
+    #[test]
+    fn test_create_bounty_refunds_overpayment() {
+        let (mut deps, env) = proper_instantiate();
+        let creator = deps.api.addr_make("creator");
+        let info = message_info(&creator, &coins(800, "token"));
+
+        let res =
+            execute_create_bounty(deps.as_mut(), env.clone(), info, bounty_msg(&env, 500)).unwrap();
+
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: creator.to_string(),
+                amount: coins(300, "token"),
+            })
+        );
+        let bounty = BOUNTIES.load(deps.as_ref().storage, 1).unwrap();
+        assert_eq!(bounty.balance, Uint128::new(500));
+    }
+
+    #[test]
+    fn test_withdraw_excess() {
+        let (mut deps, env) = proper_instantiate();
+        let creator = deps.api.addr_make("creator");
+        let info = message_info(&creator, &coins(500, "token"));
+        execute_create_bounty(deps.as_mut(), env.clone(), info, bounty_msg(&env, 500)).unwrap();
+
+        let fund = FundBountyMsg { bounty_id: 1 };
+        let info = message_info(&creator, &coins(200, "other"));
+        let err = execute_fund_bounty(deps.as_mut(), env.clone(), info, fund.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidFunds {}));
+        let info = message_info(&creator, &coins(200, "token"));
+        execute_fund_bounty(deps.as_mut(), env.clone(), info, fund).unwrap();
+        let bounty = BOUNTIES.load(deps.as_ref().storage, 1).unwrap();
+        assert_eq!(bounty.balance, Uint128::new(700));
+
+        let msg = WithdrawExcessMsg {
+            bounty_id: 1,
+            amount: Uint128::new(201),
+        };
+        let info = message_info(&creator, &[]);
+        let err =
+            execute_withdraw_excess(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidExcessWithdrawal { .. }));
+
+        let other = message_info(&deps.api.addr_make("other"), &[]);
+        let msg = WithdrawExcessMsg {
+            bounty_id: 1,
+            amount: Uint128::new(200),
+        };
+        let err =
+            execute_withdraw_excess(deps.as_mut(), env.clone(), other, msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        let res = execute_withdraw_excess(deps.as_mut(), env, info, msg).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: creator.to_string(),
+                amount: coins(200, "token"),
+            })
+        );
+        let bounty = BOUNTIES.load(deps.as_ref().storage, 1).unwrap();
+        assert_eq!(bounty.balance, Uint128::new(500));
+    }
+
+    #[test]
+    fn test_finalize_with_signature() {
+        let (mut deps, env) = proper_instantiate();
+        let creator = deps.api.addr_make("creator");
+        let developer = deps.api.addr_make("developer");
+
+        let signing_key = ed25519_zebra::SigningKey::from([7u8; 32]);
+        let pubkey: [u8; 32] = ed25519_zebra::VerificationKey::from(&signing_key).into();
+
+        let mut msg = bounty_msg(&env, 500);
+        msg.approver_key = Some(ApproverKey {
+            scheme: SignatureScheme::Ed25519,
+            pubkey: Binary::from(pubkey.as_slice()),
+        });
+        let info = message_info(&creator, &coins(500, "token"));
+        execute_create_bounty(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let payload = ApprovalPayload {
+            contract_address: env.contract.address.clone(),
+            chain_id: env.block.chain_id.clone(),
+            bounty_id: 1,
+            recipient: developer.clone(),
+            amount: Uint128::new(400),
+            nonce: 0,
+        };
+        let signature: [u8; 64] = signing_key.sign(&payload.to_bytes().unwrap()).into();
+        let finalize = SignedFinalizeMsg {
+            bounty_id: 1,
+            recipient: developer.to_string(),
+            amount: Uint128::new(400),
+            nonce: 0,
+            signature: Binary::from(signature.as_slice()),
+        };
+
+        // The signature does not cover a different amount
+        let mut tampered = finalize.clone();
+        tampered.amount = Uint128::new(500);
+        let err =
+            execute_finalize_with_signature(deps.as_mut(), env.clone(), tampered).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidSignature {}));
+
+        let res =
+            execute_finalize_with_signature(deps.as_mut(), env.clone(), finalize.clone()).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: developer.to_string(),
+                amount: coins(400, "token"),
+            })
+        );
+        assert_eq!(
+            res.messages[1].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: creator.to_string(),
+                amount: coins(100, "token"),
+            })
+        );
+
+        let bounty = BOUNTIES.load(deps.as_ref().storage, 1).unwrap();
+        assert_eq!(bounty.status, BountyStatus::Completed);
+        assert_eq!(bounty.nonce, 1);
+
+        // Replaying the same approval is rejected
+        execute_finalize_with_signature(deps.as_mut(), env, finalize).unwrap_err();
+    }
+
+    #[test]
+    fn test_oracle_attestation() {
+        let (mut deps, env) = proper_instantiate();
+        let creator = deps.api.addr_make("creator");
+        let oracle = deps.api.addr_make("oracle");
+        let developer = deps.api.addr_make("developer");
+
+        let mut msg = bounty_msg(&env, 500);
+        msg.oracle = Some(oracle.to_string());
+        msg.veto_period = Some(3600);
+        let info = message_info(&creator, &coins(500, "token"));
+        execute_create_bounty(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let attest = AttestCompletionMsg {
+            bounty_id: 1,
+            recipient: developer.to_string(),
+            evidence: "https://example.com/pull/1".to_string(),
+        };
+        let err = execute_attest_completion(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&creator, &[]),
+            attest.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        let oracle_info = message_info(&oracle, &[]);
+        execute_attest_completion(deps.as_mut(), env.clone(), oracle_info, attest).unwrap();
+
+        let err = execute_execute_attestation(
+            deps.as_mut(),
+            env.clone(),
+            ExecuteAttestationMsg { bounty_id: 1 },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::VetoWindowOpen {}));
+
+        let mut later = env.clone();
+        later.block.time = later.block.time.plus_seconds(3600);
+
+        let err = execute_veto_attestation(
+            deps.as_mut(),
+            later.clone(),
+            message_info(&creator, &[]),
+            VetoAttestationMsg { bounty_id: 1 },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::VetoWindowClosed {}));
+
+        let res = execute_execute_attestation(
+            deps.as_mut(),
+            later,
+            ExecuteAttestationMsg { bounty_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: developer.to_string(),
+                amount: coins(500, "token"),
+            })
+        );
+        let bounty = BOUNTIES.load(deps.as_ref().storage, 1).unwrap();
+        assert_eq!(bounty.status, BountyStatus::Completed);
+
+        // Refunding the bounty discards an attestation still in its veto window
+        let mut msg = bounty_msg(&env, 500);
+        msg.oracle = Some(oracle.to_string());
+        msg.veto_period = Some(3600);
+        let info = message_info(&creator, &coins(500, "token"));
+        execute_create_bounty(deps.as_mut(), env.clone(), info, msg).unwrap();
+        let attest = AttestCompletionMsg {
+            bounty_id: 2,
+            recipient: developer.to_string(),
+            evidence: "https://example.com/pull/2".to_string(),
+        };
+        execute_attest_completion(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&oracle, &[]),
+            attest,
+        )
+        .unwrap();
+        let finalize = FinalizeBountyMsg {
+            bounty_id: 2,
+            success: false,
+        };
+        execute_finalize_bounty(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&creator, &[]),
+            finalize,
+        )
+        .unwrap();
+        assert!(BOUNTIES
+            .load(deps.as_ref().storage, 2)
+            .unwrap()
+            .attestation
+            .is_none());
+
+        let mut later = env.clone();
+        later.block.time = later.block.time.plus_seconds(3600);
+        let err = execute_execute_attestation(
+            deps.as_mut(),
+            later.clone(),
+            ExecuteAttestationMsg { bounty_id: 2 },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::NoAttestation {}));
+
+        // Attested payouts are held for the release delay like any other approval
+        let mut msg = bounty_msg(&env, 500);
+        msg.oracle = Some(oracle.to_string());
+        msg.veto_period = Some(3600);
+        msg.release_delay = Some(600);
+        let info = message_info(&creator, &coins(500, "token"));
+        execute_create_bounty(deps.as_mut(), env.clone(), info, msg).unwrap();
+        let attest = AttestCompletionMsg {
+            bounty_id: 3,
+            recipient: developer.to_string(),
+            evidence: "https://example.com/pull/3".to_string(),
+        };
+        execute_attest_completion(deps.as_mut(), env, message_info(&oracle, &[]), attest).unwrap();
+        let res = execute_execute_attestation(
+            deps.as_mut(),
+            later,
+            ExecuteAttestationMsg { bounty_id: 3 },
+        )
+        .unwrap();
+        assert!(res.messages.is_empty());
+        let bounty = BOUNTIES.load(deps.as_ref().storage, 3).unwrap();
+        assert!(matches!(bounty.status, BountyStatus::PendingRelease { .. }));
+        assert_eq!(bounty.recipient, Some(developer));
+    }
+
+    #[test]
+    fn test_time_locked_payout() {
+        let (mut deps, env) = proper_instantiate();
+        let creator = deps.api.addr_make("creator");
+        let arbiter = deps.api.addr_make("arbiter");
+        let developer = deps.api.addr_make("developer");
+
+        for _ in 0..2 {
+            let mut msg = bounty_msg(&env, 500);
+            msg.recipient = Some(developer.to_string());
+            msg.release_delay = Some(600);
+            msg.arbiter = Some(arbiter.to_string());
+            let info = message_info(&creator, &coins(500, "token"));
+            execute_create_bounty(deps.as_mut(), env.clone(), info, msg).unwrap();
+        }
+
+        for bounty_id in 1..=2 {
+            let finalize = FinalizeBountyMsg {
+                bounty_id,
+                success: true,
+            };
+            let res = execute_finalize_bounty(
+                deps.as_mut(),
+                env.clone(),
+                message_info(&creator, &[]),
+                finalize,
+            )
+            .unwrap();
+            assert!(res.messages.is_empty());
+        }
+
+        let developer_info = message_info(&developer, &[]);
+        let err = execute_claim_payout(
+            deps.as_mut(),
+            env.clone(),
+            developer_info.clone(),
+            ClaimPayoutMsg { bounty_id: 1 },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::NotYetReleased { .. }));
+
+        // The arbiter freezes the second payout and sends it back to the issuer
+        let arbiter_info = message_info(&arbiter, &[]);
+        execute_freeze_payout(
+            deps.as_mut(),
+            env.clone(),
+            arbiter_info.clone(),
+            FreezePayoutMsg { bounty_id: 2 },
+        )
+        .unwrap();
+        let res = execute_resolve_frozen_payout(
+            deps.as_mut(),
+            env.clone(),
+            arbiter_info,
+            ResolveFrozenPayoutMsg {
+                bounty_id: 2,
+                release: false,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: creator.to_string(),
+                amount: coins(500, "token"),
+            })
+        );
+
+        let mut later = env.clone();
+        later.block.time = later.block.time.plus_seconds(600);
+        let res = execute_claim_payout(
+            deps.as_mut(),
+            later,
+            developer_info,
+            ClaimPayoutMsg { bounty_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: developer.to_string(),
+                amount: coins(500, "token"),
+            })
+        );
+    }
+
+    #[test]
+    fn test_vested_payout() {
+        let (mut deps, env) = proper_instantiate();
+        let creator = deps.api.addr_make("creator");
+        let developer = deps.api.addr_make("developer");
+
+        let mut msg = bounty_msg(&env, 500);
+        msg.recipient = Some(developer.to_string());
+        msg.vesting = Some(VestingTerms::Linear {
+            cliff: 100,
+            duration: 1000,
+        });
+        let info = message_info(&creator, &coins(500, "token"));
+        execute_create_bounty(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let finalize = FinalizeBountyMsg {
+            bounty_id: 1,
+            success: true,
+        };
+        let res = execute_finalize_bounty(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&creator, &[]),
+            finalize,
+        )
+        .unwrap();
+        assert!(res.messages.is_empty());
+
+        let developer_info = message_info(&developer, &[]);
+        let err = execute_claim_vested(
+            deps.as_mut(),
+            env.clone(),
+            developer_info.clone(),
+            ClaimVestedMsg { bounty_id: 1 },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::NothingToClaim {}));
+
+        let mut later = env.clone();
+        later.block.time = later.block.time.plus_seconds(500);
+        let res = execute_claim_vested(
+            deps.as_mut(),
+            later.clone(),
+            developer_info,
+            ClaimVestedMsg { bounty_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: developer.to_string(),
+                amount: coins(250, "token"),
+            })
+        );
+
+        later.block.time = later.block.time.plus_seconds(500);
+        let vesting = query_vesting(deps.as_ref(), later, 1).unwrap();
+        assert_eq!(vesting.vested, Uint128::new(500));
+        assert_eq!(vesting.claimable, Uint128::new(250));
+    }
+
+    #[test]
+    fn test_pull_payments() {
+        let (mut deps, env) = proper_instantiate();
+        let creator = deps.api.addr_make("creator");
+        let developer = deps.api.addr_make("developer");
+
+        let mut msg = bounty_msg(&env, 500);
+        msg.recipient = Some(developer.to_string());
+        msg.pull_payments = true;
+        let info = message_info(&creator, &coins(500, "token"));
+        execute_create_bounty(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let finalize = FinalizeBountyMsg {
+            bounty_id: 1,
+            success: true,
+        };
+        let res = execute_finalize_bounty(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&creator, &[]),
+            finalize,
+        )
+        .unwrap();
+        assert!(res.messages.is_empty());
+
+        let claimable = query_claimable(deps.as_ref(), developer.to_string()).unwrap();
+        assert_eq!(claimable.balances, coins(500, "token"));
+
+        let claim = ClaimMsg {
+            denoms: None,
+            ibc: None,
+        };
+        let res = execute_claim(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&developer, &[]),
+            claim.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: developer.to_string(),
+                amount: coins(500, "token"),
+            })
+        );
+
+        let developer_info = message_info(&developer, &[]);
+        let err = execute_claim(deps.as_mut(), env, developer_info, claim).unwrap_err();
+        assert!(matches!(err, ContractError::NothingToClaim {}));
+    }
+
+    #[test]
+    fn test_ibc_payout_restored_on_failure() {
+        let (mut deps, env) = proper_instantiate();
+        let creator = deps.api.addr_make("creator");
+        let developer = deps.api.addr_make("developer");
+
+        let mut msg = bounty_msg(&env, 500);
+        msg.recipient = Some(developer.to_string());
+        let info = message_info(&creator, &coins(500, "token"));
+        execute_create_bounty(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        // Only the recipient chooses where their payout goes
+        let destination = SetPayoutDestinationMsg {
+            bounty_id: 1,
+            ibc: Some(IbcDestination {
+                channel_id: "channel-0".to_string(),
+                receiver: "osmo1developer".to_string(),
+                timeout: 600,
+            }),
+        };
+        let err = execute_set_payout_destination(
+            deps.as_mut(),
+            message_info(&creator, &[]),
+            destination.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        execute_set_payout_destination(deps.as_mut(), message_info(&developer, &[]), destination)
+            .unwrap();
+
+        let finalize = FinalizeBountyMsg {
+            bounty_id: 1,
+            success: true,
+        };
+        let mut res = execute_finalize_bounty(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&creator, &[]),
+            finalize,
+        )
+        .unwrap();
+        let submsg = res.messages.remove(0);
+        assert_eq!(submsg.id, IBC_TRANSFER_REPLY_ID);
+        assert!(matches!(
+            submsg.msg,
+            CosmosMsg::Ibc(IbcMsg::Transfer { ref to_address, .. }) if to_address == "osmo1developer"
+        ));
+
+        let failed = Reply {
+            id: submsg.id,
+            payload: submsg.payload,
+            gas_used: 0,
+            result: SubMsgResult::Err("channel closed".to_string()),
+        };
+        reply(deps.as_mut(), env, failed).unwrap();
+
+        let claimable = query_claimable(deps.as_ref(), developer.to_string()).unwrap();
+        assert_eq!(claimable.balances, coins(500, "token"));
+    }
+
+    #[test]
+    fn test_hooks() {
+        let (mut deps, env) = proper_instantiate();
+        let creator = deps.api.addr_make("creator");
+        let registry = deps.api.addr_make("registry");
+        let payroll = deps.api.addr_make("payroll");
+
+        let add = HookMsg {
+            address: registry.to_string(),
+        };
+        let err =
+            execute_add_hook(deps.as_mut(), message_info(&payroll, &[]), add.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        execute_add_hook(deps.as_mut(), message_info(&creator, &[]), add).unwrap();
+
+        let mut msg = bounty_msg(&env, 500);
+        msg.hooks = vec![payroll.to_string(); MAX_HOOKS + 1];
+        let info = message_info(&creator, &coins(500, "token"));
+        let err = execute_create_bounty(deps.as_mut(), env.clone(), info.clone(), msg.clone())
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::TooManyHooks { max: MAX_HOOKS }
+        ));
+
+        msg.hooks = vec![payroll.to_string()];
+        let res = execute_create_bounty(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let payload = to_json_binary(&BountyHookExecuteMsg::BountyHook(BountyHookMsg::Created {
+            bounty_id: 1,
+            issuer: creator.clone(),
+        }))
+        .unwrap();
+        let expected: Vec<_> = [&registry, &payroll]
+            .into_iter()
+            .map(|hook| {
+                SubMsg::reply_on_error(
+                    WasmMsg::Execute {
+                        contract_addr: hook.to_string(),
+                        msg: payload.clone(),
+                        funds: vec![],
+                    },
+                    HOOK_REPLY_ID,
+                )
+                .with_gas_limit(HOOK_GAS_LIMIT)
+            })
+            .collect();
+        assert_eq!(res.messages, expected);
+
+        // A failing hook doesn't revert the action that triggered it
+        let failed = Reply {
+            id: HOOK_REPLY_ID,
+            payload: Binary::default(),
+            gas_used: 0,
+            result: SubMsgResult::Err("hook panicked".to_string()),
+        };
+        reply(deps.as_mut(), env, failed).unwrap();
+    }
+
+    #[test]
+    fn test_badge_minted_on_completion() {
+        let (mut deps, env) = proper_instantiate();
+        let creator = deps.api.addr_make("creator");
+        let developer = deps.api.addr_make("developer");
+        let badges = deps.api.addr_make("badges");
+
+        let update = UpdateConfigMsg {
+            admin: None,
+            max_title_length: None,
+            max_description_length: None,
+            badge_contract: Some(badges.to_string()),
+            staking: None,
+            price_oracle: None,
+            swap_router: None,
+            nft_contracts: None,
+        };
+        execute_update_config(deps.as_mut(), message_info(&creator, &[]), update).unwrap();
+
+        let mut msg = bounty_msg(&env, 500);
+        msg.recipient = Some(developer.to_string());
+        msg.tags = vec!["rust".to_string()];
+        let info = message_info(&creator, &coins(500, "token"));
+        execute_create_bounty(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let finalize = FinalizeBountyMsg {
+            bounty_id: 1,
+            success: true,
+        };
+        let res =
+            execute_finalize_bounty(deps.as_mut(), env, message_info(&creator, &[]), finalize)
+                .unwrap();
+
+        let mint = Cw721ExecuteMsg::Mint {
+            token_id: "bounty-1".to_string(),
+            owner: developer.to_string(),
+            token_uri: None,
+            extension: BadgeMetadata {
+                bounty_id: 1,
+                title: "Fix a bug".to_string(),
+                amount: Coin {
+                    denom: "token".to_string(),
+                    amount: Uint128::new(500),
+                },
+                tags: vec!["rust".to_string()],
+            },
+        };
+        assert_eq!(
+            res.messages[1].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: badges.to_string(),
+                msg: to_json_binary(&mint).unwrap(),
+                funds: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn test_nft_rewards() {
+        let (mut deps, env) = proper_instantiate();
+        let creator = deps.api.addr_make("creator");
+        let developer = deps.api.addr_make("developer");
+        let items = deps.api.addr_make("items");
+        let forged = deps.api.addr_make("forged");
+
+        let mut msg = bounty_msg(&env, 0);
+        msg.recipient = Some(developer.to_string());
+        let receive = Cw721ReceiveMsg {
+            sender: creator.to_string(),
+            token_id: "sword".to_string(),
+            msg: to_json_binary(&ReceiveNftMsg::CreateBounty(Box::new(msg))).unwrap(),
+        };
+
+        // Only allowlisted cw721 contracts can escrow prizes
+        let update = UpdateConfigMsg {
+            admin: None,
+            max_title_length: None,
+            max_description_length: None,
+            badge_contract: None,
+            staking: None,
+            price_oracle: None,
+            swap_router: None,
+            nft_contracts: Some(vec![items.to_string()]),
+        };
+        execute_update_config(deps.as_mut(), message_info(&creator, &[]), update).unwrap();
+        let err = execute_receive_nft(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&forged, &[]),
+            receive.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::NftContractNotAllowed {}));
+        execute_receive_nft(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&items, &[]),
+            receive,
+        )
+        .unwrap();
+
+        // Only the issuer can add prizes to its bounty
+        let add = |sender: &Addr| Cw721ReceiveMsg {
+            sender: sender.to_string(),
+            token_id: "shield".to_string(),
+            msg: to_json_binary(&ReceiveNftMsg::AddReward { bounty_id: 1 }).unwrap(),
+        };
+        let err = execute_receive_nft(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&items, &[]),
+            add(&developer),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        execute_receive_nft(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&items, &[]),
+            add(&creator),
+        )
+        .unwrap();
+
+        let finalize = FinalizeBountyMsg {
+            bounty_id: 1,
+            success: true,
+        };
+        let res =
+            execute_finalize_bounty(deps.as_mut(), env, message_info(&creator, &[]), finalize)
+                .unwrap();
+
+        let expected: Vec<_> = ["sword", "shield"]
+            .into_iter()
+            .map(|token_id| {
+                let transfer = Cw721ExecuteMsg::<Empty>::TransferNft {
+                    recipient: developer.to_string(),
+                    token_id: token_id.to_string(),
+                };
+                SubMsg::reply_on_error(
+                    WasmMsg::Execute {
+                        contract_addr: items.to_string(),
+                        msg: to_json_binary(&transfer).unwrap(),
+                        funds: vec![],
+                    },
+                    NFT_TRANSFER_REPLY_ID,
+                )
+            })
+            .collect();
+        assert_eq!(res.messages, expected);
+    }
+
+    #[test]
+    fn test_group_issuer() {
+        let (mut deps, env) = proper_instantiate();
+        let creator = deps.api.addr_make("creator");
+        let member = deps.api.addr_make("member");
+        let outsider = deps.api.addr_make("outsider");
+        let group = deps.api.addr_make("group");
+
+        let members = member.to_string();
+        deps.querier.update_wasm(move |query| {
+            let WasmQuery::Smart { msg, .. } = query else {
+                unimplemented!()
+            };
+            let Cw4QueryMsg::Member { addr, .. } = from_json(msg).unwrap() else {
+                unimplemented!()
+            };
+            let weight = (addr == members).then_some(2);
+            SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&MemberResponse { weight }).unwrap(),
+            ))
+        });
+
+        let mut msg = bounty_msg(&env, 500);
+        msg.recipient = Some(deps.api.addr_make("developer").to_string());
+        msg.issuer_group = Some(IssuerGroupMsg {
+            contract: group.to_string(),
+            min_weight: None,
+        });
+        let info = message_info(&creator, &coins(500, "token"));
+        execute_create_bounty(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let finalize = FinalizeBountyMsg {
+            bounty_id: 1,
+            success: true,
+        };
+        let err = execute_finalize_bounty(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&outsider, &[]),
+            finalize.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        execute_finalize_bounty(deps.as_mut(), env, message_info(&member, &[]), finalize).unwrap();
+        let bounty = BOUNTIES.load(&deps.storage, 1).unwrap();
+        assert_eq!(bounty.status, BountyStatus::Completed);
+    }
+
+    #[test]
+    fn test_reviewer_threshold() {
+        let (mut deps, env) = proper_instantiate();
+        let creator = deps.api.addr_make("creator");
+        let developer = deps.api.addr_make("developer");
+        let reviewers: Vec<Addr> = ["alice", "bob", "carol"]
+            .into_iter()
+            .map(|name| deps.api.addr_make(name))
+            .collect();
+
+        let mut msg = bounty_msg(&env, 500);
+        msg.recipient = Some(developer.to_string());
+        msg.reviewers = reviewers.iter().map(Addr::to_string).collect();
+        msg.threshold = 4;
+        let info = message_info(&creator, &coins(500, "token"));
+        let err = execute_create_bounty(deps.as_mut(), env.clone(), info.clone(), msg.clone())
+            .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidThreshold {}));
+
+        msg.threshold = 2;
+        msg.oracle = Some(developer.to_string());
+        let err = execute_create_bounty(deps.as_mut(), env.clone(), info.clone(), msg.clone())
+            .unwrap_err();
+        assert!(matches!(err, ContractError::ReviewersBypassed {}));
+
+        msg.oracle = None;
+        execute_create_bounty(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let vote = |success| FinalizeBountyMsg {
+            bounty_id: 1,
+            success,
+        };
+        // The issuer no longer decides alone
+        let err = execute_finalize_bounty(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&creator, &[]),
+            vote(true),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        let res = execute_finalize_bounty(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&reviewers[0], &[]),
+            vote(true),
+        )
+        .unwrap();
+        assert!(res.messages.is_empty());
+
+        let err = execute_finalize_bounty(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&reviewers[0], &[]),
+            vote(true),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::AlreadyVoted {}));
+
+        execute_finalize_bounty(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&reviewers[1], &[]),
+            vote(false),
+        )
+        .unwrap();
+        let tally = query_votes(deps.as_ref(), 1).unwrap();
+        assert_eq!((tally.approvals, tally.rejections), (1, 1));
+
+        let res = execute_finalize_bounty(
+            deps.as_mut(),
+            env,
+            message_info(&reviewers[2], &[]),
+            vote(true),
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: developer.to_string(),
+                amount: coins(500, "token"),
+            })]
+        );
+        let bounty = BOUNTIES.load(&deps.storage, 1).unwrap();
+        assert_eq!(bounty.status, BountyStatus::Completed);
+    }
+
+    #[test]
+    fn test_contest() {
+        let (mut deps, mut env) = proper_instantiate();
+        let creator = deps.api.addr_make("creator");
+        let hunters: Vec<Addr> = ["hunter1", "hunter2", "hunter3"]
+            .into_iter()
+            .map(|name| deps.api.addr_make(name))
+            .collect();
+        let voters: Vec<Addr> = ["alice", "bob", "carol"]
+            .into_iter()
+            .map(|name| deps.api.addr_make(name))
+            .collect();
+
+        let mut msg = bounty_msg(&env, 500);
+        msg.contest = Some(ContestMsg {
+            reveal_end_height: None,
+            reveal_end_time: None,
+            voting_end_height: Some(env.block.height + 200),
+            voting_end_time: None,
+            voters: VotersMsg::Whitelist(voters.iter().map(Addr::to_string).collect()),
+            prizes: vec![Uint128::new(300), Uint128::new(200)],
+        });
+        let info = message_info(&creator, &coins(500, "token"));
+        execute_create_bounty(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        for hunter in &hunters {
+            let entry = SubmitEntryMsg {
+                bounty_id: 1,
+                content: format!("entry of {hunter}"),
+            };
+            execute_submit_entry(deps.as_mut(), env.clone(), message_info(hunter, &[]), entry)
+                .unwrap();
+        }
+
+        let vote = |entry_id| VoteEntryMsg {
+            bounty_id: 1,
+            entry_id,
+        };
+        let err = execute_vote_entry(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&voters[0], &[]),
+            vote(1),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::VotingClosed {}));
+
+        env.block.height += 101;
+        let finalize = FinalizeBountyMsg {
+            bounty_id: 1,
+            success: false,
+        };
+        let err = execute_finalize_bounty(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&creator, &[]),
+            finalize,
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::ContestBounty {}));
+
+        for (voter, entry_id) in voters.iter().zip([1, 2, 2]) {
+            execute_vote_entry(
+                deps.as_mut(),
+                env.clone(),
+                message_info(voter, &[]),
+                vote(entry_id),
+            )
+            .unwrap();
+        }
+        let err = execute_vote_entry(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&hunters[0], &[]),
+            vote(1),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::NoVotingPower {}));
+
+        let settle = SettleContestMsg { bounty_id: 1 };
+        let err = execute_settle_contest(deps.as_mut(), env.clone(), settle.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::VotingNotEnded {}));
+
+        env.block.height += 100;
+        let res = execute_settle_contest(deps.as_mut(), env, settle).unwrap();
+        let expected: Vec<_> = [(&hunters[1], 300), (&hunters[0], 200)]
+            .into_iter()
+            .map(|(hunter, amount)| {
+                SubMsg::new(BankMsg::Send {
+                    to_address: hunter.to_string(),
+                    amount: coins(amount, "token"),
+                })
+            })
+            .collect();
+        assert_eq!(res.messages, expected);
+
+        let bounty = BOUNTIES.load(&deps.storage, 1).unwrap();
+        assert_eq!(bounty.status, BountyStatus::Completed);
+        assert_eq!(bounty.recipient, Some(hunters[1].clone()));
+    }
+
+    #[test]
+    fn test_contest_limits() {
+        let (mut deps, mut env) = proper_instantiate();
+        let creator = deps.api.addr_make("creator");
+        let hunter = deps.api.addr_make("hunter");
+        let voter = deps.api.addr_make("voter");
+
+        let mut msg = bounty_msg(&env, 500);
+        msg.contest = Some(ContestMsg {
+            reveal_end_height: None,
+            reveal_end_time: None,
+            voting_end_height: Some(env.block.height + 200),
+            voting_end_time: None,
+            voters: VotersMsg::Whitelist(vec![voter.to_string()]),
+            prizes: vec![Uint128::new(500)],
+        });
+        let info = message_info(&creator, &coins(500, "token"));
+
+        // Prizes overflowing the sum can't pose as the bounty quantity
+        let mut overflow = msg.clone();
+        if let Some(contest) = overflow.contest.as_mut() {
+            contest.prizes = vec![Uint128::MAX, Uint128::new(500)];
+        }
+        let err =
+            execute_create_bounty(deps.as_mut(), env.clone(), info.clone(), overflow).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidContest {}));
+        execute_create_bounty(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let entry = SubmitEntryMsg {
+            bounty_id: 1,
+            content: "entry".to_string(),
+        };
+        let info = message_info(&hunter, &[]);
+        execute_submit_entry(deps.as_mut(), env.clone(), info.clone(), entry.clone()).unwrap();
+        let err =
+            execute_submit_entry(deps.as_mut(), env.clone(), info, entry.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::AlreadyEntered {}));
+
+        for i in 1..MAX_CONTEST_ENTRIES {
+            let info = message_info(&deps.api.addr_make(&format!("hunter{i}")), &[]);
+            execute_submit_entry(deps.as_mut(), env.clone(), info, entry.clone()).unwrap();
+        }
+        let info = message_info(&deps.api.addr_make("latecomer"), &[]);
+        let err = execute_submit_entry(deps.as_mut(), env.clone(), info, entry).unwrap_err();
+        assert!(matches!(err, ContractError::TooManyEntries { .. }));
+
+        // The issuer may only take the prize back once nobody settled the contest in time
+        let expire = ExpireBountyMsg { bounty_id: 1 };
+        env.block.height += 201;
+        let info = message_info(&creator, &[]);
+        let err =
+            execute_expire_bounty(deps.as_mut(), env.clone(), info, expire.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::ContestBounty {}));
+
+        env.block.height += CONTEST_SETTLE_BLOCKS;
+        let info = message_info(&creator, &[]);
+        let res = execute_expire_bounty(deps.as_mut(), env, info, expire).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: creator.to_string(),
+                amount: coins(500, "token"),
+            })]
+        );
+    }
+
+    #[test]
+    fn test_commit_reveal_contest() {
+        let (mut deps, mut env) = proper_instantiate();
+        let creator = deps.api.addr_make("creator");
+        let hunter = deps.api.addr_make("hunter");
+        let voter = deps.api.addr_make("voter");
+
+        let mut msg = bounty_msg(&env, 500);
+        msg.contest = Some(ContestMsg {
+            reveal_end_height: Some(env.block.height + 150),
+            reveal_end_time: None,
+            voting_end_height: Some(env.block.height + 200),
+            voting_end_time: None,
+            voters: VotersMsg::Whitelist(vec![voter.to_string()]),
+            prizes: vec![Uint128::new(500)],
+        });
+        let info = message_info(&creator, &coins(500, "token"));
+        execute_create_bounty(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let entry = SubmitEntryMsg {
+            bounty_id: 1,
+            content: "my entry".to_string(),
+        };
+        let err = execute_submit_entry(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&hunter, &[]),
+            entry,
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::CommitRevealRequired {}));
+
+        let hash = Sha256::new()
+            .chain_update(hunter.as_bytes())
+            .chain_update(b"salt")
+            .chain_update(b"my entry")
+            .finalize();
+        let commit = CommitSubmissionMsg {
+            bounty_id: 1,
+            hash: Binary::from(hash.as_slice()),
+        };
+        execute_commit_submission(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&hunter, &[]),
+            commit,
+        )
+        .unwrap();
+
+        let reveal = |salt: &str| RevealSubmissionMsg {
+            bounty_id: 1,
+            payload: "my entry".to_string(),
+            salt: salt.to_string(),
+        };
+        let err = execute_reveal_submission(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&hunter, &[]),
+            reveal("salt"),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::RevealClosed {}));
+
+        env.block.height += 101;
+        let err = execute_reveal_submission(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&hunter, &[]),
+            reveal("pepper"),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::CommitmentMismatch {}));
+
+        // Voting only opens once the reveal period is over
+        let vote = VoteEntryMsg {
+            bounty_id: 1,
+            entry_id: 1,
+        };
+        let err = execute_vote_entry(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&voter, &[]),
+            vote.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::VotingClosed {}));
+
+        execute_reveal_submission(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&hunter, &[]),
+            reveal("salt"),
+        )
+        .unwrap();
+        let entries = query_entries(deps.as_ref(), 1).unwrap().entries;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].1.content, "my entry");
+
+        env.block.height += 50;
+        execute_vote_entry(deps.as_mut(), env, message_info(&voter, &[]), vote).unwrap();
+    }
+
+    #[test]
+    fn test_merkle_payouts() {
+        let (mut deps, mut env) = proper_instantiate();
+        let creator = deps.api.addr_make("creator");
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+        let carol = deps.api.addr_make("carol");
+
+        let info = message_info(&creator, &coins(500, "token"));
+        execute_create_bounty(deps.as_mut(), env.clone(), info, bounty_msg(&env, 500)).unwrap();
+
+        let leaves = [
+            leaf_hash(&alice, Uint128::new(100)),
+            leaf_hash(&bob, Uint128::new(150)),
+            leaf_hash(&carol, Uint128::new(50)),
+        ];
+        let node = hash_pair(&leaves[0], &leaves[1]);
+        let root = hash_pair(&node, &leaves[2]);
+
+        let post = PostMerkleRootMsg {
+            bounty_id: 1,
+            root: Binary::from(root.as_slice()),
+            claim_deadline: env.block.time.plus_seconds(3600),
+        };
+        execute_post_merkle_root(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&creator, &[]),
+            post,
+        )
+        .unwrap();
+
+        let claim = |amount: u128, proof: &[[u8; 32]]| ClaimMerkleMsg {
+            bounty_id: 1,
+            amount: Uint128::new(amount),
+            proof: proof
+                .iter()
+                .map(|hash| Binary::from(hash.as_slice()))
+                .collect(),
+        };
+        let err = execute_claim_merkle(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&alice, &[]),
+            claim(150, &[leaves[1], leaves[2]]),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidProof {}));
+
+        let res = execute_claim_merkle(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&alice, &[]),
+            claim(100, &[leaves[1], leaves[2]]),
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: alice.to_string(),
+                amount: coins(100, "token"),
+            })]
+        );
+        let err = execute_claim_merkle(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&alice, &[]),
+            claim(100, &[leaves[1], leaves[2]]),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::AlreadyClaimed {}));
+
+        execute_claim_merkle(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&carol, &[]),
+            claim(50, &[node]),
+        )
+        .unwrap();
+
+        let sweep = SweepMerkleMsg { bounty_id: 1 };
+        let err = execute_sweep_merkle(deps.as_mut(), env.clone(), sweep.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::ClaimDeadlineNotPassed {}));
+
+        env.block.time = env.block.time.plus_seconds(3601);
+        let res = execute_sweep_merkle(deps.as_mut(), env, sweep).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: creator.to_string(),
+                amount: coins(350, "token"),
+            })]
+        );
+        let bounty = BOUNTIES.load(&deps.storage, 1).unwrap();
+        assert_eq!(bounty.status, BountyStatus::Completed);
+    }
+
+    #[test]
+    fn test_program_bounties() {
+        let (mut deps, mut env) = proper_instantiate();
+        let owner = deps.api.addr_make("owner");
+        let reviewer = deps.api.addr_make("reviewer");
+        let outsider = deps.api.addr_make("outsider");
+
+        let program = CreateProgramMsg {
+            title: "Grants".to_string(),
+            token_denom: "token".to_string(),
+            reviewers: vec![reviewer.to_string()],
+        };
+        execute_create_program(
+            deps.as_mut(),
+            message_info(&owner, &coins(1000, "token")),
+            program,
+        )
+        .unwrap();
+
+        let mut msg = bounty_msg(&env, 400);
+        msg.program_id = Some(1);
+        let err = execute_create_bounty(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&outsider, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        let res = execute_create_bounty(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&reviewer, &[]),
+            msg.clone(),
+        )
+        .unwrap();
+        assert!(res.messages.is_empty());
+        assert_eq!(
+            PROGRAMS.load(&deps.storage, 1).unwrap().balance,
+            Uint128::new(600)
+        );
+
+        msg.quantity = Uint128::new(700);
+        let err = execute_create_bounty(deps.as_mut(), env.clone(), message_info(&owner, &[]), msg)
+            .unwrap_err();
+        assert!(matches!(err, ContractError::InsufficientFunds {}));
+
+        // The owner manages the reviewer's child bounty, and its expiry refunds the program
+        env.block.height += 101;
+        let expire = ExpireBountyMsg { bounty_id: 1 };
+        let res =
+            execute_expire_bounty(deps.as_mut(), env, message_info(&owner, &[]), expire).unwrap();
+        assert!(res.messages.is_empty());
+        assert_eq!(
+            PROGRAMS.load(&deps.storage, 1).unwrap().balance,
+            Uint128::new(1000)
+        );
+
+        let withdraw = WithdrawProgramMsg {
+            program_id: 1,
+            amount: Uint128::new(1000),
+        };
+        let res =
+            execute_withdraw_program(deps.as_mut(), message_info(&owner, &[]), withdraw).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: owner.to_string(),
+                amount: coins(1000, "token"),
+            })]
+        );
+    }
+
+    #[test]
+    fn test_recurring_bounty() {
+        let (mut deps, mut env) = proper_instantiate();
+        let creator = deps.api.addr_make("creator");
+        let developer = deps.api.addr_make("developer");
+
+        let mut msg = bounty_msg(&env, 500);
+        msg.recipient = Some(developer.to_string());
+        msg.recurrence = Some(RecurrenceMsg { max_cycles: 3 });
+        let info = message_info(&creator, &coins(500, "token"));
+        let err = execute_create_bounty(deps.as_mut(), env.clone(), info, msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::InsufficientFunds {}));
+
+        let info = message_info(&creator, &coins(1500, "token"));
+        execute_create_bounty(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        env.block.height += 10;
+        let finalize = FinalizeBountyMsg {
+            bounty_id: 1,
+            success: true,
+        };
+        let res = execute_finalize_bounty(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&creator, &[]),
+            finalize.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: developer.to_string(),
+                amount: coins(500, "token"),
+            })]
+        );
+
+        // A completed cycle can't be finalized again to skip ahead
+        let err = execute_finalize_bounty(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&creator, &[]),
+            finalize,
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::BountyClosed {}));
+
+        // The second cycle opens with a fresh deadline and the rest of the deposit
+        let renewed = BOUNTIES.load(&deps.storage, 2).unwrap();
+        assert_eq!(renewed.status, BountyStatus::Open);
+        assert_eq!(renewed.end_height, Some(env.block.height + 100));
+        assert_eq!(renewed.balance, Uint128::new(500));
+        assert_eq!(renewed.recurrence.as_ref().unwrap().cycle, 2);
+        assert_eq!(renewed.recurrence.unwrap().reserve, Uint128::new(500));
+
+        // Expiring a cycle ends the recurrence and returns the reserve too
+        env.block.height += 101;
+        let expire = ExpireBountyMsg { bounty_id: 2 };
+        let res = execute_expire_bounty(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&creator, &[]),
+            expire.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: creator.to_string(),
+                amount: coins(1000, "token"),
+            })]
+        );
+        assert!(!BOUNTIES.has(&deps.storage, 3));
+
+        let err = execute_expire_bounty(deps.as_mut(), env, message_info(&creator, &[]), expire)
+            .unwrap_err();
+        assert!(matches!(err, ContractError::BountyClosed {}));
+    }
+
+    #[test]
+    fn test_security_reports() {
+        let (mut deps, env) = proper_instantiate();
+        let creator = deps.api.addr_make("creator");
+        let reviewer = deps.api.addr_make("reviewer");
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+
+        let mut msg = bounty_msg(&env, 1000);
+        msg.reviewers = vec![reviewer.to_string()];
+        msg.threshold = 1;
+        msg.severity_tiers = Some(SeverityTiers {
+            low: Uint128::new(100),
+            medium: Uint128::new(300),
+            high: Uint128::new(600),
+            critical: Uint128::new(1000),
+        });
+        let info = message_info(&creator, &coins(1000, "token"));
+        execute_create_bounty(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        for (hunter, content) in [
+            (&alice, "Rounding error"),
+            (&bob, "Reentrancy"),
+            (&bob, "Dup"),
+        ] {
+            let submit = SubmitReportMsg {
+                bounty_id: 1,
+                content: content.to_string(),
+            };
+            execute_submit_report(
+                deps.as_mut(),
+                env.clone(),
+                message_info(hunter, &[]),
+                submit,
+            )
+            .unwrap();
+        }
+
+        // Only the issuer and reviewers rate reports
+        let rate = RateReportMsg {
+            bounty_id: 1,
+            report_id: 1,
+            severity: Some(Severity::Medium),
+        };
+        let err = execute_rate_report(deps.as_mut(), message_info(&alice, &[]), rate.clone())
+            .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        let res =
+            execute_rate_report(deps.as_mut(), message_info(&reviewer, &[]), rate.clone()).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: alice.to_string(),
+                amount: coins(300, "token"),
+            })]
+        );
+        let err =
+            execute_rate_report(deps.as_mut(), message_info(&reviewer, &[]), rate).unwrap_err();
+        assert!(matches!(err, ContractError::AlreadyRated {}));
+
+        let reject = RateReportMsg {
+            bounty_id: 1,
+            report_id: 3,
+            severity: None,
+        };
+        let res = execute_rate_report(deps.as_mut(), message_info(&creator, &[]), reject).unwrap();
+        assert!(res.messages.is_empty());
+
+        // A single finalization cannot drain the pool
+        let finalize = FinalizeBountyMsg {
+            bounty_id: 1,
+            success: true,
+        };
+        let err = execute_finalize_bounty(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&reviewer, &[]),
+            finalize,
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::SecurityPool {}));
+
+        // The critical tier is capped at what is left, completing the bounty
+        let rate = RateReportMsg {
+            bounty_id: 1,
+            report_id: 2,
+            severity: Some(Severity::Critical),
+        };
+        let res = execute_rate_report(deps.as_mut(), message_info(&creator, &[]), rate).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: bob.to_string(),
+                amount: coins(700, "token"),
+            })]
+        );
+        let bounty = BOUNTIES.load(&deps.storage, 1).unwrap();
+        assert_eq!(bounty.status, BountyStatus::Completed);
+
+        let reports = query_reports(deps.as_ref(), 1).unwrap().reports;
+        assert_eq!(reports.len(), 3);
+        assert_eq!(
+            reports[1].1.status,
+            ReportStatus::Accepted {
+                severity: Severity::Critical,
+                paid: Uint128::new(700),
+            }
+        );
+        assert_eq!(reports[2].1.status, ReportStatus::Rejected);
+    }
+
+    #[test]
+    fn test_hunter_bond() {
+        let (mut deps, mut env) = proper_instantiate();
+        let creator = deps.api.addr_make("creator");
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+
+        let mut msg = bounty_msg(&env, 500);
+        msg.hunter_bond = Some(Uint128::new(50));
+        let info = message_info(&creator, &coins(500, "token"));
+        execute_create_bounty(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let apply = ApplyMsg { bounty_id: 1 };
+        let info = message_info(&alice, &coins(40, "token"));
+        let err = execute_apply(deps.as_mut(), env.clone(), info, apply.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::InsufficientFunds {}));
+        for hunter in [&alice, &bob] {
+            let info = message_info(hunter, &coins(50, "token"));
+            execute_apply(deps.as_mut(), env.clone(), info, apply.clone()).unwrap();
+        }
+
+        let assign = AssignHunterMsg {
+            bounty_id: 1,
+            hunter: alice.to_string(),
+        };
+        let info = message_info(&creator, &[]);
+        execute_assign_hunter(deps.as_mut(), env.clone(), info, assign).unwrap();
+
+        // Applicants that were not assigned get their bond back
+        let withdraw = WithdrawApplicationMsg { bounty_id: 1 };
+        let res =
+            execute_withdraw_application(deps.as_mut(), message_info(&bob, &[]), withdraw).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: bob.to_string(),
+                amount: coins(50, "token"),
+            })]
+        );
+
+        // Abandoning slashes the bond to the issuer and reopens the bounty
+        let abandon = AbandonMsg { bounty_id: 1 };
+        let res = execute_abandon(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&alice, &[]),
+            abandon,
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: creator.to_string(),
+                amount: coins(50, "token"),
+            })]
+        );
+        let bounty = BOUNTIES.load(&deps.storage, 1).unwrap();
+        assert_eq!(bounty.recipient, None);
+        assert!(bounty.held_bond.is_zero());
+
+        // Completion returns the bond along with the payout
+        let info = message_info(&bob, &coins(50, "token"));
+        execute_apply(deps.as_mut(), env.clone(), info, apply).unwrap();
+        let assign = AssignHunterMsg {
+            bounty_id: 1,
+            hunter: bob.to_string(),
+        };
+        let info = message_info(&creator, &[]);
+        execute_assign_hunter(deps.as_mut(), env.clone(), info, assign).unwrap();
+        assert!(query_applications(deps.as_ref(), 1)
+            .unwrap()
+            .applications
+            .is_empty());
+
+        env.block.height += 10;
+        let finalize = FinalizeBountyMsg {
+            bounty_id: 1,
+            success: true,
+        };
+        let res =
+            execute_finalize_bounty(deps.as_mut(), env, message_info(&creator, &[]), finalize)
+                .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![
+                SubMsg::new(BankMsg::Send {
+                    to_address: bob.to_string(),
+                    amount: coins(500, "token"),
+                }),
+                SubMsg::new(BankMsg::Send {
+                    to_address: bob.to_string(),
+                    amount: coins(50, "token"),
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_assignment_deadline() {
+        let (mut deps, mut env) = proper_instantiate();
+        let creator = deps.api.addr_make("creator");
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+
+        let mut msg = bounty_msg(&env, 500);
+        msg.hunter_bond = Some(Uint128::new(50));
+        msg.assignment_period = Some(AssignmentPeriod {
+            blocks: Some(10),
+            seconds: None,
+        });
+        let info = message_info(&creator, &coins(500, "token"));
+        execute_create_bounty(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let assign_to = |deps: &mut TestDeps, env: &Env, hunter: &Addr| {
+            let info = message_info(hunter, &coins(50, "token"));
+            execute_apply(deps.as_mut(), env.clone(), info, ApplyMsg { bounty_id: 1 }).unwrap();
+            let assign = AssignHunterMsg {
+                bounty_id: 1,
+                hunter: hunter.to_string(),
+            };
+            let info = message_info(&creator, &[]);
+            execute_assign_hunter(deps.as_mut(), env.clone(), info, assign).unwrap();
+        };
+        assign_to(&mut deps, &env, &alice);
+
+        let release = ReleaseAssignmentMsg { bounty_id: 1 };
+        let err =
+            execute_release_assignment(deps.as_mut(), env.clone(), release.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::AssignmentActive {}));
+
+        // Anyone can release a lapsed assignment, which slashes the hunter's bond
+        env.block.height += 11;
+        let res = execute_release_assignment(deps.as_mut(), env.clone(), release.clone()).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: creator.to_string(),
+                amount: coins(50, "token"),
+            })]
+        );
+        let bounty = BOUNTIES.load(&deps.storage, 1).unwrap();
+        assert_eq!(bounty.status, BountyStatus::Open);
+        assert_eq!(bounty.recipient, None);
+        assert_eq!(bounty.assignment, None);
+
+        // A submission keeps the assignment past its deadline
+        assign_to(&mut deps, &env, &bob);
+        let submit = SubmitWorkMsg {
+            bounty_id: 1,
+            work: "https://github.com/example/repo/pull/1".to_string(),
+        };
+        let info = message_info(&alice, &[]);
+        let err =
+            execute_submit_work(deps.as_mut(), env.clone(), info, submit.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::NotAssigned {}));
+        let info = message_info(&bob, &[]);
+        execute_submit_work(deps.as_mut(), env.clone(), info, submit).unwrap();
+
+        env.block.height += 11;
+        let err = execute_release_assignment(deps.as_mut(), env.clone(), release).unwrap_err();
+        assert!(matches!(err, ContractError::AssignmentActive {}));
+
+        // Expiring the bounty returns the bond of a hunter who submitted work
+        env.block.height += 100;
+        let expire = ExpireBountyMsg { bounty_id: 1 };
+        let info = message_info(&creator, &[]);
+        let res = execute_expire_bounty(deps.as_mut(), env, info, expire).unwrap();
+        assert!(res.messages.contains(&SubMsg::new(BankMsg::Send {
+            to_address: bob.to_string(),
+            amount: coins(50, "token"),
+        })));
+    }
+
+    #[test]
+    fn test_staked_bounty() {
+        let (mut deps, mut env) = proper_instantiate();
+        let creator = deps.api.addr_make("creator");
+        let developer = deps.api.addr_make("developer");
+        let treasury = deps.api.addr_make("treasury");
+        let validator = Validator::create(
+            "validator".to_string(),
+            Decimal::percent(5),
+            Decimal::percent(10),
+            Decimal::percent(1),
+        );
+        let delegation = |amount: u128, rewards: u128| {
+            FullDelegation::create(
+                env.contract.address.clone(),
+                "validator".to_string(),
+                coin(amount, "token"),
+                coin(amount, "token"),
+                coins(rewards, "token"),
+            )
+        };
+        deps.querier
+            .staking
+            .update("token", std::slice::from_ref(&validator), &[]);
+
+        let update = UpdateConfigMsg {
+            admin: None,
+            max_title_length: None,
+            max_description_length: None,
+            badge_contract: None,
+            price_oracle: None,
+            swap_router: None,
+            nft_contracts: None,
+            staking: Some(StakingConfigMsg {
+                validator: "validator".to_string(),
+                yield_recipient: YieldRecipientMsg::Treasury(treasury.to_string()),
+                unbond_window: 3600,
+                unbond_window_blocks: 50,
+                unbonding_period: 21 * 86400,
+            }),
+        };
+        execute_update_config(deps.as_mut(), message_info(&creator, &[]), update).unwrap();
+
+        let mut msg = bounty_msg(&env, 500);
+        msg.recipient = Some(developer.to_string());
+        let info = message_info(&creator, &coins(500, "token"));
+        execute_create_bounty(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let stake = StakeBountyMsg { bounty_id: 1 };
+        let info = message_info(&creator, &[]);
+        let res = execute_stake_bounty(deps.as_mut(), env.clone(), info, stake).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(StakingMsg::Delegate {
+                validator: "validator".to_string(),
+                amount: coin(500, "token"),
+            })]
+        );
+
+        // Rewards go to the configured treasury
+        let pending = delegation(500, 20);
+        deps.querier
+            .staking
+            .update("token", std::slice::from_ref(&validator), &[pending]);
+        let harvest = HarvestYieldMsg { bounty_id: 1 };
+        let res = execute_harvest_yield(deps.as_mut(), env.clone(), harvest).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![
+                SubMsg::new(DistributionMsg::WithdrawDelegatorReward {
+                    validator: "validator".to_string(),
+                }),
+                SubMsg::new(BankMsg::Send {
+                    to_address: treasury.to_string(),
+                    amount: coins(20, "token"),
+                }),
+            ]
+        );
+        // The validator is slashed by 10%
+        let slashed = delegation(450, 0);
+        deps.querier
+            .staking
+            .update("token", &[validator], &[slashed]);
+
+        // Only the issuer can unstake before the deadline height approaches
+        let unstake = UnstakeBountyMsg { bounty_id: 1 };
+        let info = message_info(&developer, &[]);
+        let err =
+            execute_unstake_bounty(deps.as_mut(), env.clone(), info, unstake.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        env.block.height += 50;
+        let info = message_info(&developer, &[]);
+        let res = execute_unstake_bounty(deps.as_mut(), env.clone(), info, unstake).unwrap();
+        assert_eq!(
+            res.messages[1],
+            SubMsg::new(StakingMsg::Undelegate {
+                validator: "validator".to_string(),
+                amount: coin(450, "token"),
+            })
+        );
+        let bounty = BOUNTIES.load(&deps.storage, 1).unwrap();
+        assert_eq!(bounty.balance, Uint128::new(450));
+        assert_eq!(bounty.stake.unwrap().unbonding, Uint128::new(450));
+
+        // The payout waits for the funds to unbond
+        let finalize = FinalizeBountyMsg {
+            bounty_id: 1,
+            success: true,
+        };
+        let info = message_info(&creator, &[]);
+        let err = execute_finalize_bounty(deps.as_mut(), env.clone(), info, finalize.clone())
+            .unwrap_err();
+        assert!(matches!(err, ContractError::FundsStaked {}));
+
+        env.block.time = env.block.time.plus_seconds(21 * 86400 + 1);
+        let info = message_info(&creator, &[]);
+        let res = execute_finalize_bounty(deps.as_mut(), env, info, finalize).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: developer.to_string(),
+                amount: coins(450, "token"),
+            })]
+        );
+    }
+
+    #[test]
+    fn test_usd_pegged_bounty() {
+        let (mut deps, env) = proper_instantiate();
+        let creator = deps.api.addr_make("creator");
+        let developer = deps.api.addr_make("developer");
+        let oracle = deps.api.addr_make("oracle");
+
+        let mut msg = bounty_msg(&env, 500);
+        msg.recipient = Some(developer.to_string());
+        msg.usd_value = Some(Uint128::new(100));
+        let info = message_info(&creator, &coins(500, "token"));
+        let err = execute_create_bounty(deps.as_mut(), env.clone(), info.clone(), msg.clone())
+            .unwrap_err();
+        assert!(matches!(err, ContractError::PriceOracleNotSet {}));
+
+        let update = UpdateConfigMsg {
+            admin: None,
+            max_title_length: None,
+            max_description_length: None,
+            badge_contract: None,
+            staking: None,
+            price_oracle: Some(oracle.to_string()),
+            swap_router: None,
+            nft_contracts: None,
+        };
+        execute_update_config(deps.as_mut(), message_info(&creator, &[]), update).unwrap();
+        execute_create_bounty(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
+        execute_create_bounty(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let set_price = |deps: &mut TestDeps, price: Decimal| {
+            deps.querier.update_wasm(move |_| {
+                SystemResult::Ok(ContractResult::Ok(
+                    to_json_binary(&PriceResponse { price }).unwrap(),
+                ))
+            });
+        };
+        let finalize = |bounty_id| FinalizeBountyMsg {
+            bounty_id,
+            success: true,
+        };
+
+        // At $0.50 the hunter gets 200 tokens and the surplus goes back to the issuer
+        set_price(&mut deps, Decimal::percent(50));
+        let info = message_info(&creator, &[]);
+        let res = execute_finalize_bounty(deps.as_mut(), env.clone(), info, finalize(1)).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![
+                SubMsg::new(BankMsg::Send {
+                    to_address: developer.to_string(),
+                    amount: coins(200, "token"),
+                }),
+                SubMsg::new(BankMsg::Send {
+                    to_address: creator.to_string(),
+                    amount: coins(300, "token"),
+                }),
+            ]
+        );
+
+        // At $0.10 the payout is capped at the deposit
+        set_price(&mut deps, Decimal::percent(10));
+        let info = message_info(&creator, &[]);
+        let res = execute_finalize_bounty(deps.as_mut(), env.clone(), info, finalize(2)).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: developer.to_string(),
+                amount: coins(500, "token"),
+            })]
+        );
+
+        // Oracle attestations pay the pegged amount too
+        let mut msg = bounty_msg(&env, 500);
+        msg.usd_value = Some(Uint128::new(100));
+        msg.oracle = Some(oracle.to_string());
+        msg.veto_period = Some(0);
+        let info = message_info(&creator, &coins(500, "token"));
+        execute_create_bounty(deps.as_mut(), env.clone(), info, msg).unwrap();
+        let attest = AttestCompletionMsg {
+            bounty_id: 3,
+            recipient: developer.to_string(),
+            evidence: "https://example.com/pull/3".to_string(),
+        };
+        execute_attest_completion(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&oracle, &[]),
+            attest,
+        )
+        .unwrap();
+        set_price(&mut deps, Decimal::percent(50));
+        let res =
+            execute_execute_attestation(deps.as_mut(), env, ExecuteAttestationMsg { bounty_id: 3 })
+                .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![
+                SubMsg::new(BankMsg::Send {
+                    to_address: developer.to_string(),
+                    amount: coins(200, "token"),
+                }),
+                SubMsg::new(BankMsg::Send {
+                    to_address: creator.to_string(),
+                    amount: coins(300, "token"),
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_claim_and_swap() {
+        let (mut deps, env) = proper_instantiate();
+        let creator = deps.api.addr_make("creator");
+        let developer = deps.api.addr_make("developer");
+        let router = deps.api.addr_make("router");
+        credit_claimable(&mut deps.storage, &developer, "token", Uint128::new(500)).unwrap();
+
+        let claim = ClaimAndSwapMsg {
+            denom: "token".to_string(),
+            ask_denom: "usdc".to_string(),
+            min_receive: Uint128::new(490),
+        };
+        let info = message_info(&developer, &[]);
+        let err = execute_claim_and_swap(deps.as_mut(), env.clone(), info.clone(), claim.clone())
+            .unwrap_err();
+        assert!(matches!(err, ContractError::SwapRouterNotSet {}));
+
+        let update = UpdateConfigMsg {
+            admin: None,
+            max_title_length: None,
+            max_description_length: None,
+            badge_contract: None,
+            staking: None,
+            price_oracle: None,
+            swap_router: Some(router.to_string()),
+            nft_contracts: None,
+        };
+        execute_update_config(deps.as_mut(), message_info(&creator, &[]), update).unwrap();
+
+        let res = execute_claim_and_swap(deps.as_mut(), env.clone(), info, claim).unwrap();
+        let submsg = res.messages[0].clone();
+        assert_eq!(submsg.id, SWAP_REPLY_ID);
+        assert_eq!(
+            submsg.msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: router.to_string(),
+                msg: to_json_binary(&RouterExecuteMsg::Swap {
+                    ask_denom: "usdc".to_string(),
+                    minimum_receive: Some(Uint128::new(490)),
+                })
+                .unwrap(),
+                funds: coins(500, "token"),
+            })
+        );
+        assert!(query_claimable(deps.as_ref(), developer.to_string())
+            .unwrap()
+            .balances
+            .is_empty());
+
+        #[allow(deprecated)]
+        let swapped = Reply {
+            id: submsg.id,
+            payload: submsg.payload,
+            gas_used: 0,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: None,
+                msg_responses: vec![],
+            }),
+        };
+
+        // Falling short of the minimum fails the whole claim
+        let contract = env.contract.address.clone();
+        deps.querier
+            .bank
+            .update_balance(&contract, coins(480, "usdc"));
+        let err = reply(deps.as_mut(), env.clone(), swapped.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::SwapBelowMinimum { .. }));
+
+        deps.querier
+            .bank
+            .update_balance(&contract, coins(495, "usdc"));
+        let res = reply(deps.as_mut(), env, swapped).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: developer.to_string(),
+                amount: coins(495, "usdc"),
+            })]
+        );
+    }
+}
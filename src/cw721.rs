@@ -0,0 +1,35 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Binary, Coin};
+
+/// Subset of the cw721 execute API the escrow calls into
+#[cw_serde]
+pub enum Cw721ExecuteMsg<T> {
+    TransferNft {
+        recipient: String,
+        token_id: String,
+    },
+    Mint {
+        token_id: String,
+        owner: String,
+        token_uri: Option<String>,
+        extension: T,
+    },
+}
+
+/// Message a cw721 contract sends along a `SendNft` to this contract
+#[cw_serde]
+pub struct Cw721ReceiveMsg {
+    /// Previous owner of the token
+    pub sender: String,
+    pub token_id: String,
+    pub msg: Binary,
+}
+
+/// On-chain metadata of the achievement badge minted to a bounty recipient
+#[cw_serde]
+pub struct BadgeMetadata {
+    pub bounty_id: u64,
+    pub title: String,
+    pub amount: Coin,
+    pub tags: Vec<String>,
+}
@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Timestamp, Uint128, VerificationError};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,6 +6,9 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Verification(#[from] VerificationError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -23,4 +26,259 @@ pub enum ContractError {
 
     #[error("Not yet expired")]
     NotYetExpired {},
+
+    #[error("Title must not be empty")]
+    EmptyTitle {},
+
+    #[error("Title exceeds {max} bytes")]
+    TitleTooLong { max: u32 },
+
+    #[error("Description exceeds {max} bytes")]
+    DescriptionTooLong { max: u32 },
+
+    #[error("Either end_height or end_time must be set")]
+    MissingDeadline {},
+
+    #[error("Deadline is already in the past")]
+    DeadlineInPast {},
+
+    #[error("Quantity must be greater than zero")]
+    ZeroQuantity {},
+
+    #[error("Amount must be between 1 and the excess balance of {available}")]
+    InvalidExcessWithdrawal { available: Uint128 },
+
+    #[error("Invalid public key for the chosen signature scheme")]
+    InvalidPubkey {},
+
+    #[error("Bounty has no approver key")]
+    ApproverKeyNotSet {},
+
+    #[error("Invalid nonce, expected {expected}")]
+    InvalidNonce { expected: u64 },
+
+    #[error("Recipient does not match the bounty recipient")]
+    RecipientMismatch {},
+
+    #[error("Invalid signature")]
+    InvalidSignature {},
+
+    #[error("Bounty already has a pending attestation")]
+    AttestationPending {},
+
+    #[error("Bounty has no pending attestation")]
+    NoAttestation {},
+
+    #[error("Veto window has closed")]
+    VetoWindowClosed {},
+
+    #[error("Veto window is still open")]
+    VetoWindowOpen {},
+
+    #[error("Bounty payout is held or frozen")]
+    PayoutHeld {},
+
+    #[error("Bounty is already completed or expired")]
+    BountyClosed {},
+
+    #[error("Bounty has no payout pending release")]
+    NotPendingRelease {},
+
+    #[error("Payout cannot be claimed before {release_at}")]
+    NotYetReleased { release_at: Timestamp },
+
+    #[error("Challenge window has closed")]
+    ChallengeWindowClosed {},
+
+    #[error("Bounty payout is not frozen")]
+    NotFrozen {},
+
+    #[error("Vesting cliff must not exceed a non-zero duration")]
+    InvalidVestingTerms {},
+
+    #[error("Nothing to claim")]
+    NothingToClaim {},
+
+    #[error("Invalid IBC destination")]
+    InvalidIbcDestination {},
+
+    #[error("Vested payouts cannot be delivered over IBC")]
+    IbcPayoutUnsupported {},
+
+    #[error("Only unordered channels with version {version} are supported")]
+    InvalidIbcChannel { version: String },
+
+    #[error("Unknown remote bounty")]
+    UnknownRemoteBounty {},
+
+    #[error("Remote bounty already registered")]
+    RemoteBountyExists {},
+
+    #[error("Hook is already registered")]
+    HookAlreadyRegistered {},
+
+    #[error("Hook is not registered")]
+    HookNotRegistered {},
+
+    #[error("At most {max_tags} non-empty tags of up to {max_length} bytes are allowed")]
+    InvalidTags { max_tags: usize, max_length: usize },
+
+    #[error("NFTs from this contract are not accepted as prizes")]
+    NftContractNotAllowed {},
+
+    #[error("At most {max} hooks may be registered on a bounty")]
+    TooManyHooks { max: usize },
+
+    #[error("Threshold must be between 1 and the number of distinct reviewers")]
+    InvalidThreshold {},
+
+    #[error("Reviewed bounties can't be finalized by an approver key, oracle or remote chain")]
+    ReviewersBypassed {},
+
+    #[error("Reviewer has already voted")]
+    AlreadyVoted {},
+
+    #[error("Contest needs a voting end after the deadline, voters and prizes adding up to the quantity")]
+    InvalidContest {},
+
+    #[error("Contest bounties are settled by their votes")]
+    ContestBounty {},
+
+    #[error("Bounty is not a contest")]
+    NotContest {},
+
+    #[error("Submission period has ended")]
+    SubmissionsClosed {},
+
+    #[error("Entries to this contest must be committed and revealed")]
+    CommitRevealRequired {},
+
+    #[error("Contest does not take committed entries")]
+    NotCommitReveal {},
+
+    #[error("Commitment must be a 32 byte sha256 hash")]
+    InvalidCommitment {},
+
+    #[error("Reveal period is not open")]
+    RevealClosed {},
+
+    #[error("No commitment to reveal")]
+    NoCommitment {},
+
+    #[error("Revealed entry does not match its commitment")]
+    CommitmentMismatch {},
+
+    #[error("Voting period is not open")]
+    VotingClosed {},
+
+    #[error("Voting period has not ended")]
+    VotingNotEnded {},
+
+    #[error("Contest accepts at most {max} entries")]
+    TooManyEntries { max: u64 },
+
+    #[error("Hunter already entered this contest")]
+    AlreadyEntered {},
+
+    #[error("Sender has no voting power in this contest")]
+    NoVotingPower {},
+
+    #[error("Merkle root must be a 32 byte sha256 hash and the claim deadline in the future")]
+    InvalidMerkleRoot {},
+
+    #[error("Bounty is paid out through its Merkle root")]
+    MerkleDistribution {},
+
+    #[error("Bounty has no Merkle root")]
+    NoMerkleRoot {},
+
+    #[error("Invalid Merkle proof")]
+    InvalidProof {},
+
+    #[error("Payout already claimed")]
+    AlreadyClaimed {},
+
+    #[error("Claim deadline has passed")]
+    ClaimDeadlinePassed {},
+
+    #[error("Claim deadline has not passed")]
+    ClaimDeadlineNotPassed {},
+
+    #[error("Recurring bounties need at least one cycle and a non-zero quantity, and cannot be contests or Merkle distributions")]
+    InvalidRecurrence {},
+
+    #[error("Severity tiers need a non-zero tier, none above the quantity, and cannot be combined with contests, recurrence, vesting or release delays")]
+    InvalidSeverityTiers {},
+
+    #[error("Bounty has no severity tiers")]
+    NotSecurityPool {},
+
+    #[error("Severity-tiered bounties pay out per rated report")]
+    SecurityPool {},
+
+    #[error("Report has already been rated")]
+    AlreadyRated {},
+
+    #[error("Hunter bonds must be non-zero and need a single-payout bounty without a recipient")]
+    InvalidHunterBond {},
+
+    #[error("Already applied to this bounty")]
+    AlreadyApplied {},
+
+    #[error("Hunter has not applied to this bounty")]
+    NotApplied {},
+
+    #[error("Bounty already has an assigned hunter")]
+    HunterAssigned {},
+
+    #[error("Sender is not the assigned hunter")]
+    NotAssigned {},
+
+    #[error("Assignment period needs a non-zero number of blocks or seconds")]
+    InvalidAssignmentPeriod {},
+
+    #[error("Work has already been submitted")]
+    AlreadySubmitted {},
+
+    #[error("Assignment has not lapsed")]
+    AssignmentActive {},
+
+    #[error("Unknown validator")]
+    InvalidValidator {},
+
+    #[error("Staking is not configured")]
+    StakingDisabled {},
+
+    #[error("Only funded single-payout bounties in the bonded denom can be staked")]
+    NotStakeable {},
+
+    #[error("Bounty has already been staked")]
+    AlreadyStaked {},
+
+    #[error("Bounty is not staked")]
+    NotStaked {},
+
+    #[error("Bounty funds are delegated or unbonding")]
+    FundsStaked {},
+
+    #[error("USD value must be non-zero and cannot be combined with contests or severity tiers")]
+    InvalidUsdValue {},
+
+    #[error("Price oracle is not configured")]
+    PriceOracleNotSet {},
+
+    #[error("Price oracle returned a zero price")]
+    InvalidPrice {},
+
+    #[error("Swap router is not configured")]
+    SwapRouterNotSet {},
+
+    #[error("Cannot swap a denom for itself")]
+    InvalidSwap {},
+
+    #[error("Swap returned {received}, below the minimum of {min_receive}")]
+    SwapBelowMinimum {
+        received: Uint128,
+        min_receive: Uint128,
+    },
 }
@@ -0,0 +1,63 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_binary, Addr, Coin, StdResult, Storage, SubMsg, WasmMsg};
+
+use crate::state::{Bounty, HOOKS};
+
+/// Reply ID of hook callbacks, distinct from the transfer and swap reply IDs
+pub const HOOK_REPLY_ID: u64 = 3;
+
+/// Gas a single hook callback may use
+pub const HOOK_GAS_LIMIT: u64 = 500_000;
+
+/// Bounty lifecycle events delivered to hook contracts
+#[cw_serde]
+pub enum BountyHookMsg {
+    Created {
+        bounty_id: u64,
+        issuer: Addr,
+    },
+    Completed {
+        bounty_id: u64,
+        recipient: Addr,
+        amount: Coin,
+    },
+    Expired {
+        bounty_id: u64,
+    },
+    Disputed {
+        bounty_id: u64,
+    },
+}
+
+/// Execute message hook contracts must accept, `{"bounty_hook": {...}}`
+#[cw_serde]
+pub enum BountyHookExecuteMsg {
+    BountyHook(BountyHookMsg),
+}
+
+/// Build a callback with `msg` for every registered hook and every hook of the bounty.
+///
+/// Callbacks are gas limited and their failures ignored, so a broken hook can't block bounties.
+pub fn prepare_hooks(
+    storage: &dyn Storage,
+    bounty: &Bounty,
+    msg: BountyHookMsg,
+) -> StdResult<Vec<SubMsg>> {
+    let payload = to_json_binary(&BountyHookExecuteMsg::BountyHook(msg))?;
+    let global = HOOKS.may_load(storage)?.unwrap_or_default();
+    Ok(global
+        .iter()
+        .chain(bounty.hooks.iter())
+        .map(|hook| {
+            SubMsg::reply_on_error(
+                WasmMsg::Execute {
+                    contract_addr: hook.to_string(),
+                    msg: payload.clone(),
+                    funds: vec![],
+                },
+                HOOK_REPLY_ID,
+            )
+            .with_gas_limit(HOOK_GAS_LIMIT)
+        })
+        .collect())
+}
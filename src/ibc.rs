@@ -0,0 +1,427 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_json, to_json_binary, to_json_string, Addr, Binary, Coin, DepsMut, Env,
+    Ibc3ChannelOpenResponse, IbcBasicResponse, IbcCallbackRequest, IbcChannel, IbcChannelCloseMsg,
+    IbcChannelConnectMsg, IbcChannelOpenMsg, IbcChannelOpenResponse, IbcMsg, IbcOrder, IbcPacket,
+    IbcPacketAckMsg, IbcPacketReceiveMsg, IbcPacketTimeoutMsg, IbcReceiveResponse,
+    IbcSourceCallbackMsg, IbcSrcCallback, IbcTimeout, Reply, StdError, StdResult, Storage, SubMsg,
+    SubMsgResult,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::contract::finalize_bounty;
+use crate::error::ContractError;
+use crate::msg::{BountyAck, BountyPacketMsg, FinalizeBountyMsg};
+use crate::state::{
+    credit_claimable, IbcDestination, PendingRemoteBounty, PendingTransfer, BOUNTIES, CONFIG,
+    IBC_TRANSFERS, PENDING_REMOTE_BOUNTIES, REMOTE_BOUNTIES,
+};
+use crate::validation::{validate_create_bounty, validate_ibc_destination};
+
+/// Version negotiated on bounty escrow channels
+pub const IBC_VERSION: &str = "bounty-escrow-1";
+
+/// Reply ID of the submessage dispatching an ICS-20 payout
+pub const IBC_TRANSFER_REPLY_ID: u64 = 1;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_open(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelOpenMsg,
+) -> Result<IbcChannelOpenResponse, ContractError> {
+    validate_channel(msg.channel(), msg.counterparty_version())?;
+    Ok(Some(Ibc3ChannelOpenResponse {
+        version: IBC_VERSION.to_string(),
+    }))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_connect(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelConnectMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    validate_channel(msg.channel(), msg.counterparty_version())?;
+    Ok(IbcBasicResponse::new()
+        .add_attribute("action", "ibc_channel_connect")
+        .add_attribute("channel_id", &msg.channel().endpoint.channel_id))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_close(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelCloseMsg,
+) -> StdResult<IbcBasicResponse> {
+    Ok(IbcBasicResponse::new()
+        .add_attribute("action", "ibc_channel_close")
+        .add_attribute("channel_id", &msg.channel().endpoint.channel_id))
+}
+
+/// Handle a bounty packet.
+///
+/// Failures revert everything the packet changed and the chain acknowledges them with an error.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_receive(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcPacketReceiveMsg,
+) -> Result<IbcReceiveResponse, ContractError> {
+    let channel_id = msg.packet.dest.channel_id;
+    receive_bounty_packet(deps, env, &channel_id, &msg.packet.data)
+}
+
+/// The contract never sends bounty packets, so there are no acknowledgements to process
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_ack(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: IbcPacketAckMsg,
+) -> StdResult<IbcBasicResponse> {
+    Ok(IbcBasicResponse::new().add_attribute("action", "ibc_packet_ack"))
+}
+
+/// The contract never sends bounty packets, so there are no timeouts to process
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_timeout(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: IbcPacketTimeoutMsg,
+) -> StdResult<IbcBasicResponse> {
+    Ok(IbcBasicResponse::new().add_attribute("action", "ibc_packet_timeout"))
+}
+
+/// Bounty escrow channels are unordered and speak `IBC_VERSION` on both ends
+fn validate_channel(
+    channel: &IbcChannel,
+    counterparty_version: Option<&str>,
+) -> Result<(), ContractError> {
+    if channel.order != IbcOrder::Unordered
+        || channel.version != IBC_VERSION
+        || counterparty_version.is_some_and(|v| v != IBC_VERSION)
+    {
+        return Err(ContractError::InvalidIbcChannel {
+            version: IBC_VERSION.to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn receive_bounty_packet(
+    deps: DepsMut,
+    env: Env,
+    channel_id: &str,
+    data: &Binary,
+) -> Result<IbcReceiveResponse, ContractError> {
+    match from_json(data)? {
+        BountyPacketMsg::CreateBounty {
+            remote_id,
+            bounty,
+            refund_to,
+        } => {
+            let key = (channel_id, remote_id);
+            if PENDING_REMOTE_BOUNTIES.has(deps.storage, key)
+                || REMOTE_BOUNTIES.has(deps.storage, key)
+            {
+                return Err(ContractError::RemoteBountyExists {});
+            }
+
+            let config = CONFIG.load(deps.storage)?;
+            validate_create_bounty(&config, &env, &bounty, &[])?;
+            // The controller finalizes remote bounties, so reviewers would never get a vote
+            if !bounty.reviewers.is_empty() {
+                return Err(ContractError::ReviewersBypassed {});
+            }
+            if let Some(destination) = &refund_to {
+                validate_ibc_destination(destination)?;
+            }
+
+            PENDING_REMOTE_BOUNTIES.save(
+                deps.storage,
+                key,
+                &PendingRemoteBounty {
+                    bounty: *bounty,
+                    refund_to,
+                },
+            )?;
+
+            Ok(IbcReceiveResponse::new(ack_success(Binary::default())?)
+                .add_attribute("action", "register_remote_bounty")
+                .add_attribute("channel_id", channel_id)
+                .add_attribute("remote_id", remote_id.to_string()))
+        }
+        BountyPacketMsg::FinalizeBounty { remote_id, success } => {
+            let bounty_id = REMOTE_BOUNTIES
+                .may_load(deps.storage, (channel_id, remote_id))?
+                .ok_or(ContractError::UnknownRemoteBounty {})?;
+            let bounty = BOUNTIES.load(deps.storage, bounty_id)?;
+
+            let msg = FinalizeBountyMsg { bounty_id, success };
+            let res = finalize_bounty(deps, &env, bounty, msg)?;
+
+            Ok(
+                IbcReceiveResponse::new(ack_success(to_json_binary(&bounty_id)?)?)
+                    .add_submessages(res.messages)
+                    .add_attributes(res.attributes)
+                    .add_events(res.events),
+            )
+        }
+    }
+}
+
+fn ack_success(data: Binary) -> StdResult<Binary> {
+    to_json_binary(&BountyAck::Result(data))
+}
+
+/// ICS-20 acknowledgement, as written by the transfer module
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Ics20Ack {
+    Result(String),
+    Error(String),
+}
+
+/// Build an ICS-20 transfer of `amount` to `recipient`'s account on another chain.
+///
+/// The transfer is tracked until the packet is acknowledged; on failure or
+/// timeout the amount is credited to `recipient`'s claimable balance instead.
+pub fn ibc_payout(
+    env: &Env,
+    recipient: &Addr,
+    amount: Coin,
+    destination: &IbcDestination,
+) -> StdResult<SubMsg> {
+    let memo = to_json_string(&IbcCallbackRequest::source(IbcSrcCallback {
+        address: env.contract.address.clone(),
+        gas_limit: None,
+    }))?;
+    let transfer = IbcMsg::Transfer {
+        channel_id: destination.channel_id.clone(),
+        to_address: destination.receiver.clone(),
+        amount: amount.clone(),
+        timeout: IbcTimeout::with_timestamp(env.block.time.plus_seconds(destination.timeout)),
+        memo: Some(memo),
+    };
+    let pending = PendingTransfer {
+        recipient: recipient.clone(),
+        channel_id: destination.channel_id.clone(),
+        amount,
+    };
+    Ok(SubMsg::reply_always(transfer, IBC_TRANSFER_REPLY_ID)
+        .with_payload(to_json_binary(&pending)?))
+}
+
+/// Track a dispatched transfer by its packet sequence, or restore the funds if dispatch failed
+pub fn handle_transfer_reply(deps: DepsMut, reply: Reply) -> Result<(), ContractError> {
+    let pending: PendingTransfer = from_json(&reply.payload)?;
+    match reply.result {
+        SubMsgResult::Ok(response) => {
+            let data = response
+                .msg_responses
+                .first()
+                .map(|r| r.value.as_slice())
+                .unwrap_or_default();
+            let sequence = parse_transfer_sequence(data)?;
+            IBC_TRANSFERS.save(deps.storage, (&pending.channel_id, sequence), &pending)?;
+        }
+        SubMsgResult::Err(_) => restore(deps.storage, &pending)?,
+    }
+    Ok(())
+}
+
+/// Settle tracked transfers once the ICS-20 packet is acknowledged or times out
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_source_callback(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcSourceCallbackMsg,
+) -> StdResult<IbcBasicResponse> {
+    let (packet, failed) = match msg {
+        IbcSourceCallbackMsg::Acknowledgement(ack) => {
+            let failed = !matches!(
+                from_json(&ack.acknowledgement.data),
+                Ok(Ics20Ack::Result(_))
+            );
+            (ack.original_packet, failed)
+        }
+        IbcSourceCallbackMsg::Timeout(timeout) => (timeout.packet, true),
+    };
+
+    let Some(pending) = take_transfer(deps.storage, &packet)? else {
+        return Ok(IbcBasicResponse::new().add_attribute("action", "ibc_source_callback"));
+    };
+    if failed {
+        restore(deps.storage, &pending)?;
+    }
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("action", "ibc_source_callback")
+        .add_attribute("recipient", pending.recipient)
+        .add_attribute("restored", failed.to_string()))
+}
+
+/// Remove and return the transfer tracked for `packet`, if any
+fn take_transfer(
+    storage: &mut dyn Storage,
+    packet: &IbcPacket,
+) -> StdResult<Option<PendingTransfer>> {
+    let key = (packet.src.channel_id.as_str(), packet.sequence);
+    let pending = IBC_TRANSFERS.may_load(storage, key)?;
+    IBC_TRANSFERS.remove(storage, key);
+    Ok(pending)
+}
+
+/// Credit the funds of a failed transfer back to the recipient's claimable balance
+fn restore(storage: &mut dyn Storage, pending: &PendingTransfer) -> StdResult<()> {
+    credit_claimable(
+        storage,
+        &pending.recipient,
+        &pending.amount.denom,
+        pending.amount.amount,
+    )
+}
+
+/// Read the packet sequence from an encoded `MsgTransferResponse`, whose only field is the
+/// varint `sequence = 1`
+fn parse_transfer_sequence(data: &[u8]) -> StdResult<u64> {
+    let invalid = || StdError::generic_err("Invalid MsgTransferResponse");
+    let [0x08, varint @ ..] = data else {
+        return Err(invalid());
+    };
+    let mut sequence = 0u64;
+    for (i, byte) in varint.iter().take(10).enumerate() {
+        sequence |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok(sequence);
+        }
+    }
+    Err(invalid())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contract::{execute_expire_bounty, execute_fund_remote_bounty, instantiate};
+    use crate::msg::{
+        CreateBountyMsg, ExpireBountyMsg, FundRemoteBountyMsg, InstantiateMsg, RecurrenceMsg,
+    };
+    use crate::state::BountyStatus;
+    use cosmwasm_std::testing::{
+        message_info, mock_dependencies, mock_env, mock_ibc_channel_open_init, mock_ibc_packet_recv,
+    };
+    use cosmwasm_std::{coins, BankMsg, CosmosMsg, Uint128};
+
+    #[test]
+    fn test_channel_version() {
+        let mut deps = mock_dependencies();
+        let open = mock_ibc_channel_open_init("channel-0", IbcOrder::Ordered, IBC_VERSION);
+        ibc_channel_open(deps.as_mut(), mock_env(), open).unwrap_err();
+        let open = mock_ibc_channel_open_init("channel-0", IbcOrder::Unordered, "ics20-1");
+        ibc_channel_open(deps.as_mut(), mock_env(), open).unwrap_err();
+        let open = mock_ibc_channel_open_init("channel-0", IbcOrder::Unordered, IBC_VERSION);
+        ibc_channel_open(deps.as_mut(), mock_env(), open).unwrap();
+    }
+
+    #[test]
+    fn test_remote_bounty_lifecycle() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let hook_sender = deps.api.addr_make("hook_sender");
+        let developer = deps.api.addr_make("developer");
+
+        let msg = InstantiateMsg {
+            start_bounty_id: 1,
+            admin: None,
+            max_title_length: None,
+            max_description_length: None,
+            badge_contract: None,
+            staking: None,
+            price_oracle: None,
+            swap_router: None,
+            nft_contracts: None,
+        };
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let packet = BountyPacketMsg::CreateBounty {
+            remote_id: 7,
+            bounty: Box::new(CreateBountyMsg {
+                title: "Port the indexer".to_string(),
+                description: "Port the indexer to the new chain".to_string(),
+                recipient: Some(developer.to_string()),
+                end_height: Some(env.block.height + 100),
+                token_denom: "token".to_string(),
+                quantity: Uint128::new(500),
+                recurrence: Some(RecurrenceMsg { max_cycles: 2 }),
+                ..Default::default()
+            }),
+            refund_to: None,
+        };
+        let recv = mock_ibc_packet_recv("channel-0", &packet).unwrap();
+        let res = ibc_packet_receive(deps.as_mut(), env.clone(), recv).unwrap();
+        let ack: BountyAck = from_json(res.acknowledgement.unwrap()).unwrap();
+        assert_eq!(ack, BountyAck::Result(Binary::default()));
+
+        // Registering the same remote bounty twice fails the packet
+        let recv = mock_ibc_packet_recv("channel-0", &packet).unwrap();
+        let err = ibc_packet_receive(deps.as_mut(), env.clone(), recv).unwrap_err();
+        assert!(matches!(err, ContractError::RemoteBountyExists {}));
+
+        let fund = FundRemoteBountyMsg {
+            channel_id: "channel-0".to_string(),
+            remote_id: 7,
+        };
+        let info = message_info(&hook_sender, &coins(1000, "token"));
+        execute_fund_remote_bounty(deps.as_mut(), env.clone(), info, fund).unwrap();
+
+        // Funding the bounty doesn't let the funder manage it
+        let mut later = env.clone();
+        later.block.height += 101;
+        let info = message_info(&hook_sender, &[]);
+        let expire = ExpireBountyMsg { bounty_id: 1 };
+        let err = execute_expire_bounty(deps.as_mut(), later, info, expire).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        let packet = BountyPacketMsg::FinalizeBounty {
+            remote_id: 7,
+            success: true,
+        };
+        let recv = mock_ibc_packet_recv("channel-0", &packet).unwrap();
+        let res = ibc_packet_receive(deps.as_mut(), env, recv).unwrap();
+        let ack: BountyAck = from_json(res.acknowledgement.unwrap()).unwrap();
+        assert_eq!(ack, BountyAck::Result(to_json_binary(&1u64).unwrap()));
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: developer.to_string(),
+                amount: coins(500, "token"),
+            })
+        );
+
+        let bounty = BOUNTIES.load(deps.as_ref().storage, 1).unwrap();
+        assert_eq!(bounty.status, BountyStatus::Completed);
+        assert_eq!(bounty.issuer, hook_sender);
+
+        // The next cycle stays under the controller
+        let renewed = REMOTE_BOUNTIES
+            .load(deps.as_ref().storage, ("channel-0", 7))
+            .unwrap();
+        assert_eq!(renewed, 2);
+        let bounty = BOUNTIES.load(deps.as_ref().storage, renewed).unwrap();
+        assert_eq!(bounty.status, BountyStatus::Open);
+        assert_eq!(
+            bounty.controller,
+            BOUNTIES.load(deps.as_ref().storage, 1).unwrap().controller
+        );
+    }
+
+    #[test]
+    fn test_parse_transfer_sequence() {
+        assert_eq!(parse_transfer_sequence(&[0x08, 0x01]).unwrap(), 1);
+        assert_eq!(parse_transfer_sequence(&[0x08, 0xac, 0x02]).unwrap(), 300);
+        parse_transfer_sequence(&[]).unwrap_err();
+        parse_transfer_sequence(&[0x08, 0x80]).unwrap_err();
+    }
+}
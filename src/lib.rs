@@ -1,8 +1,17 @@
 pub mod contract;
+pub mod cw721;
 mod error;
 pub mod helpers;
+pub mod hooks;
+pub mod ibc;
+pub mod merkle;
 {% unless minimal %}pub mod integration_tests;
 {% endunless %}pub mod msg;
+pub mod oracle;
+pub mod router;
+pub mod signature;
+pub mod staking;
 pub mod state;
+pub mod validation;
 
 pub use crate::error::ContractError;
@@ -0,0 +1,29 @@
+use cosmwasm_std::{Addr, Binary, Uint128};
+use sha2::{Digest, Sha256};
+
+/// Leaf of a payout tree, the sha256 hash of the recipient address followed by the decimal amount
+pub fn leaf_hash(recipient: &Addr, amount: Uint128) -> [u8; 32] {
+    Sha256::new()
+        .chain_update(recipient.as_bytes())
+        .chain_update(amount.to_string().as_bytes())
+        .finalize()
+        .into()
+}
+
+/// Hash of two sibling nodes, ordered so proofs need no left or right flags
+pub fn hash_pair(a: &[u8], b: &[u8]) -> [u8; 32] {
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+    Sha256::new()
+        .chain_update(first)
+        .chain_update(second)
+        .finalize()
+        .into()
+}
+
+/// Check that `proof` links `leaf` up to `root`
+pub fn verify_proof(root: &Binary, leaf: [u8; 32], proof: &[Binary]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(leaf, |node, sibling| hash_pair(&node, sibling));
+    computed.as_slice() == root.as_slice()
+}
@@ -1,14 +1,75 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Timestamp, Uint128};
+use cosmwasm_std::{Addr, Binary, Coin, Timestamp, Uint128};
+
+use crate::cw721::Cw721ReceiveMsg;
+use crate::state::{
+    ApproverKey, AssignmentPeriod, Entry, IbcDestination, Report, Severity, SeverityTiers,
+    VestingSchedule, VestingTerms,
+};
 
 /// Instantiate message to initialize contract state
 #[cw_serde]
 pub struct InstantiateMsg {
     pub start_bounty_id: u64,
+    /// Address allowed to update the config, defaults to the instantiator
+    pub admin: Option<String>,
+    pub max_title_length: Option<u32>,
+    pub max_description_length: Option<u32>,
+    /// cw721 contract minting achievement badges on completion
+    pub badge_contract: Option<String>,
+    /// Validator escrowed funds can be delegated to
+    pub staking: Option<StakingConfigMsg>,
+    /// Oracle contract pricing USD-pegged bounties
+    pub price_oracle: Option<String>,
+    /// DEX router swapping claimed payouts to other denoms
+    pub swap_router: Option<String>,
+    /// cw721 contracts accepted as prize escrow, none when unset
+    pub nft_contracts: Option<Vec<String>>,
+}
+
+/// Message to update the contract configuration
+#[cw_serde]
+pub struct UpdateConfigMsg {
+    pub admin: Option<String>,
+    pub max_title_length: Option<u32>,
+    pub max_description_length: Option<u32>,
+    pub badge_contract: Option<String>,
+    pub staking: Option<StakingConfigMsg>,
+    pub price_oracle: Option<String>,
+    pub swap_router: Option<String>,
+    /// Replaces the list of accepted cw721 contracts
+    pub nft_contracts: Option<Vec<String>>,
+}
+
+/// Delegation of escrowed funds to a validator, with rewards going to `yield_recipient`
+#[cw_serde]
+pub struct StakingConfigMsg {
+    pub validator: String,
+    pub yield_recipient: YieldRecipientMsg,
+    /// Seconds before a bounty's deadline time from which anyone may undelegate it
+    pub unbond_window: u64,
+    /// Blocks before a bounty's deadline height from which anyone may undelegate it
+    pub unbond_window_blocks: u64,
+    /// Unbonding period of the chain, in seconds, which the contract can't query itself
+    pub unbonding_period: u64,
+}
+
+#[cw_serde]
+pub enum YieldRecipientMsg {
+    Issuer,
+    Hunter,
+    Treasury(String),
+}
+
+/// Message to add or remove a hook contract
+#[cw_serde]
+pub struct HookMsg {
+    pub address: String,
 }
 
 /// Message to create a new bounty
 #[cw_serde]
+#[derive(Default)]
 pub struct CreateBountyMsg {
     pub title: String,
     pub description: String,
@@ -17,6 +78,182 @@ pub struct CreateBountyMsg {
     pub end_time: Option<Timestamp>,
     pub token_denom: String,
     pub quantity: Uint128,
+    /// Key allowed to approve payouts off-chain via `FinalizeWithSignature`
+    pub approver_key: Option<ApproverKey>,
+    /// Address allowed to attest completion via `AttestCompletion`
+    pub oracle: Option<String>,
+    /// Seconds the issuer has to veto an attestation, defaults to one day
+    pub veto_period: Option<u64>,
+    /// When set, approved payouts are held for this many seconds before they can be claimed
+    pub release_delay: Option<u64>,
+    /// Address allowed, besides the admin, to freeze a held payout
+    pub arbiter: Option<String>,
+    /// Vest the payout to the recipient instead of sending it on completion
+    pub vesting: Option<VestingTerms>,
+    /// Credit payouts and refunds to a claimable balance instead of sending them
+    #[serde(default)]
+    pub pull_payments: bool,
+    /// Contracts to call back on this bounty's events
+    #[serde(default)]
+    pub hooks: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// cw4 group whose members may act as the issuer
+    pub issuer_group: Option<IssuerGroupMsg>,
+    /// Addresses voting on `FinalizeBounty` instead of the issuer deciding alone
+    #[serde(default)]
+    pub reviewers: Vec<String>,
+    /// Number of matching reviewer votes needed to pay out or refund the bounty
+    #[serde(default)]
+    pub threshold: u32,
+    /// Run the bounty as a contest, taking entries until the deadline
+    pub contest: Option<ContestMsg>,
+    /// Fund the bounty from this program's budget instead of the sent funds
+    pub program_id: Option<u64>,
+    /// Reopen the bounty with the same parameters when it completes
+    pub recurrence: Option<RecurrenceMsg>,
+    /// Pay each rated report its severity tier from the bounty pool
+    pub severity_tiers: Option<SeverityTiers>,
+    /// Bond hunters must post to apply, refunded unless they abandon or miss the deadline
+    pub hunter_bond: Option<Uint128>,
+    /// Time an assigned hunter has to submit work before anyone can release the assignment
+    pub assignment_period: Option<AssignmentPeriod>,
+    /// Pay this USD value at the oracle price on completion, with the quantity as a cap
+    pub usd_value: Option<Uint128>,
+}
+
+/// Renewal of a bounty on completion.
+///
+/// Without a program, the sent funds must cover `quantity` for every cycle upfront.
+#[cw_serde]
+pub struct RecurrenceMsg {
+    /// Total number of cycles, including the first one
+    pub max_cycles: u32,
+}
+
+/// Voting rules and prizes of a contest bounty
+#[cw_serde]
+pub struct ContestMsg {
+    /// Take entries as `CommitSubmission` hashes, revealed between the deadline and this end
+    pub reveal_end_height: Option<u64>,
+    pub reveal_end_time: Option<Timestamp>,
+    pub voting_end_height: Option<u64>,
+    pub voting_end_time: Option<Timestamp>,
+    pub voters: VotersMsg,
+    /// Prizes by rank, adding up to the bounty quantity
+    pub prizes: Vec<Uint128>,
+}
+
+/// Who may vote on contest entries
+#[cw_serde]
+pub enum VotersMsg {
+    Whitelist(Vec<String>),
+    /// DAO staking contract answering `VotingPowerAtHeight`
+    Staking(String),
+}
+
+/// cw4 group allowed to manage a bounty alongside its issuer
+#[cw_serde]
+pub struct IssuerGroupMsg {
+    pub contract: String,
+    /// Minimum member weight required to act as the issuer, defaults to 1
+    pub min_weight: Option<u64>,
+}
+
+/// Message attached to a cw721 `SendNft` depositing the token as a bounty prize
+#[cw_serde]
+pub enum ReceiveNftMsg {
+    /// Create a bounty with the token as its prize, `quantity` may be zero
+    CreateBounty(Box<CreateBountyMsg>),
+    /// Add the token to the prizes of an open bounty of the sender
+    AddReward { bounty_id: u64 },
+}
+
+/// Message to create a program, funded with the sent coins
+#[cw_serde]
+pub struct CreateProgramMsg {
+    pub title: String,
+    pub token_denom: String,
+    /// Addresses allowed, besides the owner, to create and manage child bounties
+    #[serde(default)]
+    pub reviewers: Vec<String>,
+}
+
+/// Message to add the sent coins to a program's budget
+#[cw_serde]
+pub struct FundProgramMsg {
+    pub program_id: u64,
+}
+
+/// Message for the program owner to withdraw uncommitted budget
+#[cw_serde]
+pub struct WithdrawProgramMsg {
+    pub program_id: u64,
+    pub amount: Uint128,
+}
+
+/// Message to apply to a bounty, sending its hunter bond if it has one
+#[cw_serde]
+pub struct ApplyMsg {
+    pub bounty_id: u64,
+}
+
+/// Message to assign a bounty to one of its applicants
+#[cw_serde]
+pub struct AssignHunterMsg {
+    pub bounty_id: u64,
+    pub hunter: String,
+}
+
+/// Message for the assigned hunter to give up a bounty, forfeiting their bond
+#[cw_serde]
+pub struct AbandonMsg {
+    pub bounty_id: u64,
+}
+
+/// Message to withdraw an application that was not assigned, returning its bond
+#[cw_serde]
+pub struct WithdrawApplicationMsg {
+    pub bounty_id: u64,
+}
+
+/// Message for the assigned hunter to submit their work
+#[cw_serde]
+pub struct SubmitWorkMsg {
+    pub bounty_id: u64,
+    pub work: String,
+}
+
+/// Message to release an assignment whose deadline passed without a submission
+#[cw_serde]
+pub struct ReleaseAssignmentMsg {
+    pub bounty_id: u64,
+}
+
+/// Message to delegate a bounty's balance to the configured validator
+#[cw_serde]
+pub struct StakeBountyMsg {
+    pub bounty_id: u64,
+}
+
+/// Message to undelegate a bounty's balance ahead of its payout
+#[cw_serde]
+pub struct UnstakeBountyMsg {
+    pub bounty_id: u64,
+}
+
+/// Message to pay out the yield a staked bounty earned so far
+#[cw_serde]
+pub struct HarvestYieldMsg {
+    pub bounty_id: u64,
+}
+
+/// Message to fund a bounty registered over IBC, sent by an ICS-20 memo hook
+#[cw_serde]
+pub struct FundRemoteBountyMsg {
+    /// Local end of the channel the bounty was registered on
+    pub channel_id: String,
+    pub remote_id: u64,
 }
 
 /// Message to finalize a bounty
@@ -26,18 +263,253 @@ pub struct FinalizeBountyMsg {
     pub success: bool, // true if successful, false if not
 }
 
+/// Message for a bounty's recipient to receive its payout on another chain
+#[cw_serde]
+pub struct SetPayoutDestinationMsg {
+    pub bounty_id: u64,
+    /// Account on another chain to deliver the payout to, a local send when unset
+    pub ibc: Option<IbcDestination>,
+}
+
+/// Message to finalize a bounty with an approval signed by the bounty's approver key
+#[cw_serde]
+pub struct SignedFinalizeMsg {
+    pub bounty_id: u64,
+    pub recipient: String,
+    pub amount: Uint128,
+    pub nonce: u64,
+    /// Signature over the canonical `ApprovalPayload`
+    pub signature: Binary,
+}
+
+/// Message from the bounty oracle attesting the bounty was completed
+#[cw_serde]
+pub struct AttestCompletionMsg {
+    pub bounty_id: u64,
+    pub recipient: String,
+    pub evidence: String,
+}
+
+/// Message to veto a pending oracle attestation
+#[cw_serde]
+pub struct VetoAttestationMsg {
+    pub bounty_id: u64,
+}
+
+/// Message to pay out an attestation once its veto window has passed
+#[cw_serde]
+pub struct ExecuteAttestationMsg {
+    pub bounty_id: u64,
+}
+
+/// Message for the recipient to claim a held payout once its release time has passed
+#[cw_serde]
+pub struct ClaimPayoutMsg {
+    pub bounty_id: u64,
+}
+
+/// Message to freeze a held payout during its challenge window
+#[cw_serde]
+pub struct FreezePayoutMsg {
+    pub bounty_id: u64,
+}
+
+/// Message to settle a frozen payout
+#[cw_serde]
+pub struct ResolveFrozenPayoutMsg {
+    pub bounty_id: u64,
+    /// true pays the recipient, false refunds the issuer
+    pub release: bool,
+}
+
+/// Message for the recipient to claim vested funds of a completed bounty
+#[cw_serde]
+pub struct ClaimVestedMsg {
+    pub bounty_id: u64,
+}
+
+/// Message to withdraw claimable balances credited by pull-payment bounties
+#[cw_serde]
+pub struct ClaimMsg {
+    /// Only claim these denoms, all of them when unset
+    pub denoms: Option<Vec<String>>,
+    /// Deliver the claimed funds to an account on another chain
+    pub ibc: Option<IbcDestination>,
+}
+
+/// Message to withdraw a claimable balance swapped to another denom through the router
+#[cw_serde]
+pub struct ClaimAndSwapMsg {
+    /// Claimable denom to swap
+    pub denom: String,
+    /// Denom to receive
+    pub ask_denom: String,
+    /// Smallest amount of `ask_denom` to accept, failing the claim otherwise
+    pub min_receive: Uint128,
+}
+
+/// Message to submit an entry to a contest bounty
+#[cw_serde]
+pub struct SubmitEntryMsg {
+    pub bounty_id: u64,
+    pub content: String,
+}
+
+/// Message to commit to an entry of a commit-reveal contest before the deadline
+#[cw_serde]
+pub struct CommitSubmissionMsg {
+    pub bounty_id: u64,
+    /// sha256 of the hunter address, the salt and the payload, concatenated
+    pub hash: Binary,
+}
+
+/// Message to reveal a committed entry between the deadline and the end of the reveal period
+#[cw_serde]
+pub struct RevealSubmissionMsg {
+    pub bounty_id: u64,
+    pub payload: String,
+    pub salt: String,
+}
+
+/// Message to vote for a contest entry
+#[cw_serde]
+pub struct VoteEntryMsg {
+    pub bounty_id: u64,
+    pub entry_id: u64,
+}
+
+/// Message to rank the entries of a contest once voting ended and pay out its prizes
+#[cw_serde]
+pub struct SettleContestMsg {
+    pub bounty_id: u64,
+}
+
+/// Message to submit a report to a severity-tiered bounty
+#[cw_serde]
+pub struct SubmitReportMsg {
+    pub bounty_id: u64,
+    pub content: String,
+}
+
+/// Message to rate a report, paying its severity tier
+#[cw_serde]
+pub struct RateReportMsg {
+    pub bounty_id: u64,
+    pub report_id: u64,
+    /// None rejects the report
+    pub severity: Option<Severity>,
+}
+
+/// Message to pay out a bounty to many recipients through a Merkle root
+#[cw_serde]
+pub struct PostMerkleRootMsg {
+    pub bounty_id: u64,
+    /// Root of a sorted-pair sha256 tree over `sha256(address ++ amount)` leaves
+    pub root: Binary,
+    pub claim_deadline: Timestamp,
+}
+
+/// Message to claim a Merkle payout of a bounty
+#[cw_serde]
+pub struct ClaimMerkleMsg {
+    pub bounty_id: u64,
+    pub amount: Uint128,
+    /// Sibling hashes from the leaf up to the root
+    pub proof: Vec<Binary>,
+}
+
+/// Message to return unclaimed Merkle payouts to the issuer after the claim deadline
+#[cw_serde]
+pub struct SweepMerkleMsg {
+    pub bounty_id: u64,
+}
+
 /// Message to expire a bounty
 #[cw_serde]
 pub struct ExpireBountyMsg {
     pub bounty_id: u64,
 }
 
+/// Message for the issuer to add the sent coins to a bounty's balance
+#[cw_serde]
+pub struct FundBountyMsg {
+    pub bounty_id: u64,
+}
+
+/// Message to withdraw the part of a bounty balance above its quantity
+#[cw_serde]
+pub struct WithdrawExcessMsg {
+    pub bounty_id: u64,
+    pub amount: Uint128,
+}
+
 /// Messages for executing contract actions
 #[cw_serde]
 pub enum ExecuteMsg {
-    CreateBounty(CreateBountyMsg),
+    CreateBounty(Box<CreateBountyMsg>),
+    ReceiveNft(Cw721ReceiveMsg),
+    FundRemoteBounty(FundRemoteBountyMsg),
+    Apply(ApplyMsg),
+    AssignHunter(AssignHunterMsg),
+    Abandon(AbandonMsg),
+    WithdrawApplication(WithdrawApplicationMsg),
+    SubmitWork(SubmitWorkMsg),
+    ReleaseAssignment(ReleaseAssignmentMsg),
     FinalizeBounty(FinalizeBountyMsg),
+    SetPayoutDestination(SetPayoutDestinationMsg),
+    FinalizeWithSignature(SignedFinalizeMsg),
+    AttestCompletion(AttestCompletionMsg),
+    VetoAttestation(VetoAttestationMsg),
+    ExecuteAttestation(ExecuteAttestationMsg),
+    ClaimPayout(ClaimPayoutMsg),
+    FreezePayout(FreezePayoutMsg),
+    ResolveFrozenPayout(ResolveFrozenPayoutMsg),
+    ClaimVested(ClaimVestedMsg),
+    Claim(ClaimMsg),
+    ClaimAndSwap(ClaimAndSwapMsg),
+    SubmitEntry(SubmitEntryMsg),
+    CommitSubmission(CommitSubmissionMsg),
+    RevealSubmission(RevealSubmissionMsg),
+    VoteEntry(VoteEntryMsg),
+    SettleContest(SettleContestMsg),
+    SubmitReport(SubmitReportMsg),
+    RateReport(RateReportMsg),
+    PostMerkleRoot(PostMerkleRootMsg),
+    ClaimMerkle(ClaimMerkleMsg),
+    SweepMerkle(SweepMerkleMsg),
     ExpireBounty(ExpireBountyMsg),
+    UpdateConfig(UpdateConfigMsg),
+    FundBounty(FundBountyMsg),
+    AddHook(HookMsg),
+    RemoveHook(HookMsg),
+    WithdrawExcess(WithdrawExcessMsg),
+    StakeBounty(StakeBountyMsg),
+    UnstakeBounty(UnstakeBountyMsg),
+    HarvestYield(HarvestYieldMsg),
+    CreateProgram(CreateProgramMsg),
+    FundProgram(FundProgramMsg),
+    WithdrawProgram(WithdrawProgramMsg),
+}
+
+/// Packets a controller contract on another chain sends over a bounty escrow channel
+#[cw_serde]
+pub enum BountyPacketMsg {
+    /// Register a bounty, opened once its funds arrive through `FundRemoteBounty`
+    CreateBounty {
+        remote_id: u64,
+        bounty: Box<CreateBountyMsg>,
+        refund_to: Option<IbcDestination>,
+    },
+    /// Finalize a funded bounty, as its issuer would with `FinalizeBounty`
+    FinalizeBounty { remote_id: u64, success: bool },
+}
+
+/// Acknowledgement written for every received bounty packet
+#[cw_serde]
+pub enum BountyAck {
+    Result(Binary),
+    /// Written by the chain when handling the packet fails
+    Error(String),
 }
 
 /// Query messages for reading contract state
@@ -51,6 +523,42 @@ pub enum QueryMsg {
     /// List all bounties
     #[returns(Vec<BountyResponse>)]
     ListBounties {},
+
+    /// Fetch the contract configuration
+    #[returns(crate::state::Config)]
+    Config {},
+
+    /// List the hook contracts called back on every bounty
+    #[returns(HooksResponse)]
+    Hooks {},
+
+    /// Fetch the vesting schedule of a completed bounty at the current block
+    #[returns(VestingResponse)]
+    Vesting(QueryBountyMsg),
+
+    /// Fetch the balances an address can withdraw with `Claim`
+    #[returns(ClaimableResponse)]
+    Claimable { address: String },
+
+    /// List the pending applications to a bounty
+    #[returns(ApplicationsResponse)]
+    Applications(QueryBountyMsg),
+
+    /// Fetch the reviewer votes cast on a bounty
+    #[returns(VotesResponse)]
+    Votes(QueryBountyMsg),
+
+    /// List the entries of a contest bounty
+    #[returns(EntriesResponse)]
+    Entries(QueryBountyMsg),
+
+    /// List the reports of a severity-tiered bounty
+    #[returns(ReportsResponse)]
+    Reports(QueryBountyMsg),
+
+    /// Fetch a program by ID
+    #[returns(crate::state::Program)]
+    Program { program_id: u64 },
 }
 
 /// Message to query a single bounty
@@ -73,3 +581,54 @@ pub struct BountyResponse {
     pub quantity: Uint128,
     pub balance: Uint128,
 }
+
+/// Response for a vesting schedule query
+#[cw_serde]
+pub struct VestingResponse {
+    pub schedule: VestingSchedule,
+    pub vested: Uint128,
+    pub claimable: Uint128,
+}
+
+/// Response for a claimable balances query
+#[cw_serde]
+pub struct ClaimableResponse {
+    pub balances: Vec<Coin>,
+}
+
+/// Response for an applications query
+#[cw_serde]
+pub struct ApplicationsResponse {
+    /// Applicants with the bond they posted
+    pub applications: Vec<(Addr, Uint128)>,
+}
+
+/// Response for a votes query
+#[cw_serde]
+pub struct VotesResponse {
+    pub threshold: u32,
+    pub approvals: u32,
+    pub rejections: u32,
+    /// Reviewers who voted, with `true` for approve
+    pub votes: Vec<(Addr, bool)>,
+}
+
+/// Response for a contest entries query
+#[cw_serde]
+pub struct EntriesResponse {
+    /// Entries with their IDs, in submission order
+    pub entries: Vec<(u64, Entry)>,
+}
+
+/// Response for a reports query
+#[cw_serde]
+pub struct ReportsResponse {
+    /// Reports with their IDs, in submission order
+    pub reports: Vec<(u64, Report)>,
+}
+
+/// Response for a hooks query
+#[cw_serde]
+pub struct HooksResponse {
+    pub hooks: Vec<Addr>,
+}
@@ -0,0 +1,14 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Decimal;
+
+/// Price query of an oracle contract, pricing USD-pegged bounties
+#[cw_serde]
+pub enum OracleQueryMsg {
+    /// USD price of one unit of `denom`
+    Price { denom: String },
+}
+
+#[cw_serde]
+pub struct PriceResponse {
+    pub price: Decimal,
+}
@@ -0,0 +1,12 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Uint128;
+
+/// Swap through a DEX router contract, converting claimed payouts to the recipient's denom
+#[cw_serde]
+pub enum RouterExecuteMsg {
+    /// Swap the sent funds for `ask_denom`, returned to the sender
+    Swap {
+        ask_denom: String,
+        minimum_receive: Option<Uint128>,
+    },
+}
@@ -0,0 +1,48 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_vec, Addr, Api, Binary, StdResult, Uint128};
+use sha2::{Digest, Sha256};
+
+use crate::error::ContractError;
+use crate::state::{ApproverKey, SignatureScheme};
+
+/// Canonical payload signed off-chain to approve a bounty payout
+#[cw_serde]
+pub struct ApprovalPayload {
+    pub contract_address: Addr,
+    pub chain_id: String,
+    pub bounty_id: u64,
+    pub recipient: Addr,
+    pub amount: Uint128,
+    pub nonce: u64,
+}
+
+impl ApprovalPayload {
+    /// Bytes covered by the signature, the compact JSON encoding of the payload
+    pub fn to_bytes(&self) -> StdResult<Vec<u8>> {
+        to_json_vec(self)
+    }
+}
+
+/// Verify `signature` over `payload` with the approver key.
+///
+/// Secp256k1 signatures are made over the sha256 hash of the payload bytes,
+/// ed25519 signatures over the payload bytes themselves.
+pub fn verify_approval(
+    api: &dyn Api,
+    key: &ApproverKey,
+    payload: &ApprovalPayload,
+    signature: &Binary,
+) -> Result<(), ContractError> {
+    let message = payload.to_bytes()?;
+    let valid = match key.scheme {
+        SignatureScheme::Secp256k1 => {
+            let hash = Sha256::digest(&message);
+            api.secp256k1_verify(&hash, signature, &key.pubkey)?
+        }
+        SignatureScheme::Ed25519 => api.ed25519_verify(&message, signature, &key.pubkey)?,
+    };
+    if !valid {
+        return Err(ContractError::InvalidSignature {});
+    }
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Uint128;
+
+/// Voting power query of a DAO staking contract, weighing contest voters
+#[cw_serde]
+pub enum StakingQueryMsg {
+    VotingPowerAtHeight {
+        address: String,
+        height: Option<u64>,
+    },
+}
+
+#[cw_serde]
+pub struct VotingPowerAtHeightResponse {
+    pub power: Uint128,
+    pub height: u64,
+}
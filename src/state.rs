@@ -1,18 +1,52 @@
-use std::str::from_utf8;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    Addr, Binary, Coin, Decimal, Env, StdError, StdResult, Storage, Timestamp, Uint128,
+};
+use cw_storage_plus::{Item, Map};
 
-use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
-use snafu::{OptionExt, ResultExt};
+use crate::msg::CreateBountyMsg;
 
-use cosmwasm::errors::{ContractErr, ParseErr, Result, SerializeErr, Unauthorized, Utf8Err};
-use cosmwasm::query::perform_raw_query;
-use cosmwasm::serde::{from_slice, to_vec};
-use cosmwasm::storage::Storage;
-use cosmwasm::types::{Coin, CosmosMsg, Params, QueryResponse, RawQuery, Response};
+/// Contract-wide configuration
+#[cw_serde]
+pub struct Config {
+    pub admin: Addr,
+    pub max_title_length: u32,
+    pub max_description_length: u32,
+    /// cw721 contract minting achievement badges to bounty recipients
+    pub badge_contract: Option<Addr>,
+    /// Delegation of escrowed funds, disabled when unset
+    pub staking: Option<StakingConfig>,
+    /// Contract pricing the deposits of USD-pegged bounties
+    pub price_oracle: Option<Addr>,
+    /// DEX router swapping claimed payouts to the recipient's preferred denom
+    pub swap_router: Option<Addr>,
+    /// cw721 contracts whose tokens may be escrowed as bounty prizes
+    pub nft_contracts: Vec<Addr>,
+}
 
-use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Storage, StdResult, Timestamp, Uint128};
-use cw_storage_plus::{Item, Map};
+/// Validator escrowed funds can be delegated to, and who earns the rewards
+#[cw_serde]
+pub struct StakingConfig {
+    pub validator: String,
+    pub yield_recipient: YieldRecipient,
+    /// Seconds before a bounty's deadline time from which anyone may undelegate it
+    pub unbond_window: u64,
+    /// Blocks before a bounty's deadline height from which anyone may undelegate it
+    pub unbond_window_blocks: u64,
+    /// Seconds undelegated funds take to become liquid again.
+    ///
+    /// Contracts can't query the chain's unbonding time, so this must be at least as long.
+    pub unbonding_period: u64,
+}
+
+/// Receiver of the staking rewards a bounty earns
+#[cw_serde]
+pub enum YieldRecipient {
+    Issuer,
+    /// The assigned hunter, or the issuer while there is none
+    Hunter,
+    Treasury(Addr),
+}
 
 /// Represents a bounty
 #[cw_serde]
@@ -27,6 +61,427 @@ pub struct Bounty {
     pub token_denom: String,
     pub quantity: Uint128,
     pub balance: Uint128,
+    /// Key whose signatures can finalize the bounty on the issuer's behalf
+    pub approver_key: Option<ApproverKey>,
+    /// Expected nonce of the next signed approval
+    pub nonce: u64,
+    /// Address allowed to attest completion without the issuer
+    pub oracle: Option<Addr>,
+    /// Seconds the issuer has to veto an oracle attestation
+    pub veto_period: u64,
+    /// Pending oracle attestation, payable once the veto window closes
+    pub attestation: Option<Attestation>,
+    /// Seconds an approved payout waits before the recipient can claim it
+    pub release_delay: Option<u64>,
+    /// Address allowed, besides the admin, to freeze a pending payout
+    pub arbiter: Option<Addr>,
+    /// When set, completion vests the payout to the recipient instead of sending it
+    pub vesting: Option<VestingTerms>,
+    /// Credit payouts and refunds to `CLAIMABLE` instead of sending them
+    pub pull_payments: bool,
+    /// Deliver the recipient's payout over IBC instead of a local send, as chosen by the recipient
+    pub payout_destination: Option<IbcDestination>,
+    /// Controller contract on another chain managing this bounty
+    pub controller: Option<RemoteController>,
+    /// Contracts called back on this bounty's events, besides the registered `HOOKS`
+    pub hooks: Vec<Addr>,
+    pub tags: Vec<String>,
+    /// cw721 tokens paid to the recipient on completion, besides any coins
+    pub nft_rewards: Vec<NftReward>,
+    /// cw4 group whose members may act as the issuer
+    pub issuer_group: Option<IssuerGroup>,
+    /// When set, `FinalizeBounty` records votes of these reviewers instead of deciding at once
+    pub reviewers: Vec<Addr>,
+    /// Number of matching reviewer votes that decide the bounty
+    pub threshold: u32,
+    /// Makes the bounty a contest, with entries until the deadline and voting after it
+    pub contest: Option<Contest>,
+    /// Batch payout posted by the issuer, claimed by each recipient with a Merkle proof
+    pub merkle: Option<MerkleDistribution>,
+    /// Program the bounty was funded from, which refunds go back to
+    pub program_id: Option<u64>,
+    /// Reopens the bounty as a new one when it completes
+    pub recurrence: Option<Recurrence>,
+    /// Pays each rated report the amount of its severity tier from the balance
+    pub security: Option<SecurityPool>,
+    /// Refundable bond hunters post with their application
+    pub hunter_bond: Option<Uint128>,
+    /// Bond of the assigned hunter, returned on completion and slashed to the issuer if they
+    /// abandon the bounty or miss its deadline
+    pub held_bond: Uint128,
+    /// Time an assigned hunter has to submit work before the assignment can be released
+    pub assignment_period: Option<AssignmentPeriod>,
+    /// Deadline and submission of the assigned hunter
+    pub assignment: Option<Assignment>,
+    /// Delegation of the balance to the configured validator
+    pub stake: Option<Stake>,
+    /// USD value paid out at the oracle price on completion, capped at the balance
+    pub usd_value: Option<Uint128>,
+}
+
+impl Bounty {
+    /// Change the recipient, dropping a payout destination the previous one chose
+    pub fn set_recipient(&mut self, recipient: Option<Addr>) {
+        if self.recipient != recipient {
+            self.payout_destination = None;
+        }
+        self.recipient = recipient;
+    }
+}
+
+/// Balance of a bounty delegated to the configured validator
+#[cw_serde]
+pub struct Stake {
+    pub amount: Uint128,
+    /// `YIELD_INDEX` when the bounty's yield was last paid out
+    pub index: Decimal,
+    /// Amount undelegated and not yet back in the contract
+    pub unbonding: Uint128,
+    /// When `unbonding` is back in the contract
+    pub unbonding_until: Option<Timestamp>,
+}
+
+impl Stake {
+    /// Whether the funds are neither delegated nor unbonding
+    pub fn liquid(&self, env: &Env) -> bool {
+        self.amount.is_zero()
+            && (self.unbonding.is_zero()
+                || self
+                    .unbonding_until
+                    .is_some_and(|until| env.block.time > until))
+    }
+}
+
+/// Blocks and seconds a hunter has to submit work once assigned
+#[cw_serde]
+pub struct AssignmentPeriod {
+    pub blocks: Option<u64>,
+    pub seconds: Option<u64>,
+}
+
+impl AssignmentPeriod {
+    /// Assignment starting at the current block
+    pub fn start(&self, env: &Env) -> Assignment {
+        Assignment {
+            end_height: self.blocks.map(|blocks| env.block.height + blocks),
+            end_time: self
+                .seconds
+                .map(|seconds| env.block.time.plus_seconds(seconds)),
+            submission: None,
+        }
+    }
+}
+
+/// Assignment of a bounty to its recipient
+#[cw_serde]
+pub struct Assignment {
+    pub end_height: Option<u64>,
+    pub end_time: Option<Timestamp>,
+    /// Work submitted by the hunter
+    pub submission: Option<String>,
+}
+
+impl Assignment {
+    /// Whether the deadline passed without a submission
+    pub fn lapsed(&self, env: &Env) -> bool {
+        self.submission.is_none() && passed(env, self.end_height, self.end_time)
+    }
+}
+
+/// Severity a reviewer assigns to a security report
+#[cw_serde]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+/// Amount paid for a report of each severity
+#[cw_serde]
+pub struct SeverityTiers {
+    pub low: Uint128,
+    pub medium: Uint128,
+    pub high: Uint128,
+    pub critical: Uint128,
+}
+
+impl SeverityTiers {
+    pub fn amount(&self, severity: &Severity) -> Uint128 {
+        match severity {
+            Severity::Low => self.low,
+            Severity::Medium => self.medium,
+            Severity::High => self.high,
+            Severity::Critical => self.critical,
+        }
+    }
+}
+
+/// Severity-tiered bounty paying many reports from a shared pool
+#[cw_serde]
+pub struct SecurityPool {
+    pub tiers: SeverityTiers,
+    pub report_count: u64,
+}
+
+/// Report submitted to a security pool
+#[cw_serde]
+pub struct Report {
+    pub hunter: Addr,
+    pub content: String,
+    pub status: ReportStatus,
+}
+
+#[cw_serde]
+pub enum ReportStatus {
+    Pending,
+    Accepted { severity: Severity, paid: Uint128 },
+    Rejected,
+}
+
+/// Renewal of a bounty on completion, funded from the program or a reserve deposited upfront
+#[cw_serde]
+pub struct Recurrence {
+    pub max_cycles: u32,
+    /// Cycle of this bounty, the first one being 1
+    pub cycle: u32,
+    /// Deposit set aside for the remaining cycles, unused for program bounties
+    pub reserve: Uint128,
+    /// Blocks and seconds each cycle stays open for
+    pub span_height: Option<u64>,
+    pub span_seconds: Option<u64>,
+    /// Recipient every cycle starts with
+    pub recipient: Option<Addr>,
+}
+
+/// Pooled budget child bounties are funded from
+#[cw_serde]
+pub struct Program {
+    pub title: String,
+    pub owner: Addr,
+    /// Addresses allowed, besides the owner, to create and manage child bounties
+    pub reviewers: Vec<Addr>,
+    pub token_denom: String,
+    /// Budget not committed to any child bounty
+    pub balance: Uint128,
+}
+
+impl Program {
+    /// Whether `addr` may create and manage child bounties
+    pub fn is_manager(&self, addr: &Addr) -> bool {
+        self.owner == *addr || self.reviewers.contains(addr)
+    }
+}
+
+/// Merkle root of `(address, amount)` payouts of a bounty
+#[cw_serde]
+pub struct MerkleDistribution {
+    pub root: Binary,
+    /// Unclaimed payouts can be swept back to the issuer after this time
+    pub claim_deadline: Timestamp,
+}
+
+/// Blocks anyone may settle a contest for once voting ends on a height
+pub const CONTEST_SETTLE_BLOCKS: u64 = 100_800;
+
+/// Seconds anyone may settle a contest for once voting ends on a time
+pub const CONTEST_SETTLE_SECONDS: u64 = 604_800;
+
+/// Voting rules and prize table of a contest bounty
+#[cw_serde]
+pub struct Contest {
+    /// Set on commit-reveal contests, where entries are revealed between the deadline and here
+    pub reveal_end_height: Option<u64>,
+    pub reveal_end_time: Option<Timestamp>,
+    pub voting_end_height: Option<u64>,
+    pub voting_end_time: Option<Timestamp>,
+    pub voters: Voters,
+    /// Prizes by rank, best ranked entry first
+    pub prizes: Vec<Uint128>,
+    /// Height staked voting power is read at
+    pub snapshot_height: u64,
+    pub entry_count: u64,
+}
+
+impl Contest {
+    /// Whether hunters commit to a hash of their entry before revealing it
+    pub fn commit_reveal(&self) -> bool {
+        self.reveal_end_height.is_some() || self.reveal_end_time.is_some()
+    }
+
+    /// Whether the reveal period is over, which is always the case without one
+    pub fn reveal_ended(&self, env: &Env) -> bool {
+        !self.commit_reveal() || passed(env, self.reveal_end_height, self.reveal_end_time)
+    }
+
+    /// Whether the voting period is over
+    pub fn voting_ended(&self, env: &Env) -> bool {
+        passed(env, self.voting_end_height, self.voting_end_time)
+    }
+
+    /// Whether the settlement period after voting is over, letting the issuer expire the
+    /// contest instead
+    pub fn settle_ended(&self, env: &Env) -> bool {
+        self.voting_end_height
+            .is_some_and(|h| env.block.height > h + CONTEST_SETTLE_BLOCKS)
+            || self
+                .voting_end_time
+                .is_some_and(|t| env.block.time > t.plus_seconds(CONTEST_SETTLE_SECONDS))
+    }
+}
+
+/// Whether the current block is past either bound
+fn passed(env: &Env, height: Option<u64>, time: Option<Timestamp>) -> bool {
+    height.is_some_and(|h| env.block.height > h) || time.is_some_and(|t| env.block.time > t)
+}
+
+/// Who may vote on contest entries
+#[cw_serde]
+pub enum Voters {
+    /// One vote per listed address
+    Whitelist(Vec<Addr>),
+    /// Votes weighted by the voting power of a DAO staking contract
+    Staking(Addr),
+}
+
+/// Entry submitted to a contest bounty
+#[cw_serde]
+pub struct Entry {
+    pub hunter: Addr,
+    pub content: String,
+    pub votes: Uint128,
+}
+
+/// cw4 group sharing the issuer's permissions on a bounty
+#[cw_serde]
+pub struct IssuerGroup {
+    pub contract: Addr,
+    /// Minimum member weight required to act as the issuer
+    pub min_weight: u64,
+}
+
+/// cw721 token held in escrow as a bounty prize
+#[cw_serde]
+pub struct NftReward {
+    pub contract: Addr,
+    pub token_id: String,
+}
+
+/// Reference to the controller of a bounty created over IBC
+#[cw_serde]
+pub struct RemoteController {
+    /// Local end of the channel the controller speaks on
+    pub channel_id: String,
+    /// ID the controller assigned to the bounty
+    pub remote_id: u64,
+    /// Where refunds are sent back to, instead of the funding address
+    pub refund_to: Option<IbcDestination>,
+}
+
+/// Bounty registered by a remote controller, waiting for its funds
+#[cw_serde]
+pub struct PendingRemoteBounty {
+    pub bounty: CreateBountyMsg,
+    pub refund_to: Option<IbcDestination>,
+}
+
+/// Account on another chain to deliver a payout to over ICS-20
+#[cw_serde]
+pub struct IbcDestination {
+    pub channel_id: String,
+    pub receiver: String,
+    /// Seconds before the transfer packet times out
+    pub timeout: u64,
+}
+
+/// ICS-20 payout awaiting acknowledgement
+#[cw_serde]
+pub struct PendingTransfer {
+    pub recipient: Addr,
+    pub channel_id: String,
+    pub amount: Coin,
+}
+
+/// Claimed payout being swapped by the router, settled in the reply
+#[cw_serde]
+pub struct PendingSwap {
+    pub recipient: Addr,
+    pub ask_denom: String,
+    pub min_receive: Uint128,
+    /// Contract balance of `ask_denom` before the swap
+    pub balance_before: Uint128,
+}
+
+/// How a vested payout is released over time, durations in seconds
+#[cw_serde]
+pub enum VestingTerms {
+    /// Vests linearly over `duration`, with nothing claimable before `cliff`
+    Linear { cliff: u64, duration: u64 },
+    /// Vests in full once `duration` has passed
+    Cliff { duration: u64 },
+}
+
+/// Vesting schedule created when a vesting bounty completes
+#[cw_serde]
+pub struct VestingSchedule {
+    pub recipient: Addr,
+    pub denom: String,
+    pub total: Uint128,
+    pub claimed: Uint128,
+    pub start: Timestamp,
+    pub terms: VestingTerms,
+}
+
+impl VestingSchedule {
+    /// Amount vested at `now`, claimed or not
+    pub fn vested(&self, now: Timestamp) -> Uint128 {
+        let elapsed = now.seconds().saturating_sub(self.start.seconds());
+        match self.terms {
+            VestingTerms::Linear { cliff, duration } => {
+                if elapsed < cliff {
+                    Uint128::zero()
+                } else if elapsed >= duration {
+                    self.total
+                } else {
+                    self.total.multiply_ratio(elapsed, duration)
+                }
+            }
+            VestingTerms::Cliff { duration } => {
+                if elapsed >= duration {
+                    self.total
+                } else {
+                    Uint128::zero()
+                }
+            }
+        }
+    }
+
+    /// Amount vested at `now` that has not been claimed yet
+    pub fn claimable(&self, now: Timestamp) -> Uint128 {
+        self.vested(now) - self.claimed
+    }
+}
+
+/// Completion attested by the bounty oracle
+#[cw_serde]
+pub struct Attestation {
+    pub recipient: Addr,
+    pub evidence: String,
+    pub executable_at: Timestamp,
+}
+
+/// Public key used to verify off-chain approvals
+#[cw_serde]
+pub struct ApproverKey {
+    pub scheme: SignatureScheme,
+    pub pubkey: Binary,
+}
+
+/// Signature schemes supported for off-chain approvals
+#[cw_serde]
+pub enum SignatureScheme {
+    Secp256k1,
+    Ed25519,
 }
 
 /// Status of the bounty
@@ -34,28 +489,109 @@ pub struct Bounty {
 pub enum BountyStatus {
     Open,
     InProgress,
+    /// Payout approved, claimable by the recipient from `release_at`
+    PendingRelease {
+        release_at: Timestamp,
+    },
+    /// Pending payout frozen by the arbiter or admin
+    Frozen,
     Completed,
     Expired,
 }
+
+/// Item to store the contract configuration
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// Contracts called back on every bounty's events, managed by the admin
+pub const HOOKS: Item<Vec<Addr>> = Item::new("hooks");
+
 /// Map to store all bounties
 pub const BOUNTIES: Map<u64, Bounty> = Map::new("bounties");
 
+/// Reviewer votes on a bounty, `true` to approve
+pub const VOTES: Map<(u64, &Addr), bool> = Map::new("votes");
+
+/// Contest entries, keyed by bounty ID and entry ID
+pub const ENTRIES: Map<(u64, u64), Entry> = Map::new("entries");
+
+/// Total amount delegated across all bounties
+pub const TOTAL_STAKED: Item<Uint128> = Item::new("total_staked");
+
+/// Staking rewards earned per delegated token since instantiation
+pub const YIELD_INDEX: Item<Decimal> = Item::new("yield_index");
+
+/// Bonds posted by hunters applying to a bounty, keyed by bounty ID and applicant
+pub const APPLICATIONS: Map<(u64, &Addr), Uint128> = Map::new("applications");
+
+/// Reports to security pools, keyed by bounty ID and report ID
+pub const REPORTS: Map<(u64, u64), Report> = Map::new("reports");
+
+/// Recipients that claimed their Merkle payout, keyed by bounty ID and recipient
+pub const MERKLE_CLAIMED: Map<(u64, &Addr), bool> = Map::new("merkle_claimed");
+
+/// Entry ID of each hunter in a contest, keyed by bounty ID and hunter
+pub const ENTRANTS: Map<(u64, &Addr), u64> = Map::new("entrants");
+
+/// Entry hashes committed by hunters of commit-reveal contests, keyed by bounty ID and hunter
+pub const COMMITMENTS: Map<(u64, &Addr), Binary> = Map::new("commitments");
+
+/// Entry each voter voted for, keyed by bounty ID and voter
+pub const ENTRY_VOTES: Map<(u64, &Addr), u64> = Map::new("entry_votes");
+
+/// Vesting schedules of completed bounties, keyed by bounty ID
+pub const VESTING: Map<u64, VestingSchedule> = Map::new("vesting");
+
+/// Balances credited by pull-payment bounties, keyed by owner and denom
+pub const CLAIMABLE: Map<(&Addr, &str), Uint128> = Map::new("claimable");
+
+/// ICS-20 payouts in flight, keyed by source channel and packet sequence
+pub const IBC_TRANSFERS: Map<(&str, u64), PendingTransfer> = Map::new("ibc_transfers");
+
+/// Remote bounties awaiting funds, keyed by channel and remote ID
+pub const PENDING_REMOTE_BOUNTIES: Map<(&str, u64), PendingRemoteBounty> =
+    Map::new("pending_remote_bounties");
+
+/// Local IDs of funded remote bounties, keyed by channel and remote ID.
+///
+/// Recurring bounties point to their current cycle.
+pub const REMOTE_BOUNTIES: Map<(&str, u64), u64> = Map::new("remote_bounties");
+
+/// Bounty programs, keyed by program ID
+pub const PROGRAMS: Map<u64, Program> = Map::new("programs");
+
+/// Item to track the next program ID
+pub const NEXT_PROGRAM_ID: Item<u64> = Item::new("next_program_id");
+
 /// Item to track the next bounty ID
 pub const NEXT_BOUNTY_ID: Item<u64> = Item::new("next_bounty_id");
 
 /// Function to return the bounty ID of a specific bounty based on the owner
-pub fn bounty_id_by_owner(
-    storage: &dyn Storage,
-    owner: &Addr,
-) -> StdResult<Option<u64>> {
-    let mut bounty_ids = BOUNTIES.keys(storage, None, None, cosmwasm_std::Order::Ascending);
+pub fn bounty_id_by_owner(storage: &dyn Storage, owner: &Addr) -> StdResult<Option<u64>> {
+    let bounty_ids = BOUNTIES.keys(storage, None, None, cosmwasm_std::Order::Ascending);
 
-    while let Some(id) = bounty_ids.next() {
+    for id in bounty_ids {
         let bounty_id = id?;
         let bounty = BOUNTIES.load(storage, bounty_id)?;
-        if &bounty.issuer == owner {
+        if bounty.issuer == *owner {
             return Ok(Some(bounty_id));
         }
+    }
+
+    Ok(None)
+}
+
+/// Add `amount` to the balance `owner` can withdraw with `Claim`
+pub fn credit_claimable(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    denom: &str,
+    amount: Uint128,
+) -> StdResult<()> {
+    CLAIMABLE.update(storage, (owner, denom), |balance| -> StdResult<_> {
+        Ok(balance.unwrap_or_default() + amount)
+    })?;
+    Ok(())
+}
 
 /// Helper to check if a bounty is expired
 pub fn check_expired(bounty: &Bounty, env: &Env) -> bool {
@@ -79,8 +615,4 @@ pub fn ensure_bounty_open(bounty: &Bounty) -> StdResult<()> {
     } else {
         Ok(())
     }
-}
-    }
-
-    Ok(None)
 }
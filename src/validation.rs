@@ -0,0 +1,308 @@
+use cosmwasm_std::{Coin, Env, Timestamp, Uint128};
+
+use crate::error::ContractError;
+use crate::msg::{ContestMsg, CreateBountyMsg, VotersMsg};
+use crate::state::{
+    ApproverKey, Config, IbcDestination, NftReward, SeverityTiers, SignatureScheme, VestingTerms,
+};
+
+/// Default maximum title length, in bytes
+pub const DEFAULT_MAX_TITLE_LENGTH: u32 = 128;
+
+/// Default maximum description length, in bytes
+pub const DEFAULT_MAX_DESCRIPTION_LENGTH: u32 = 4096;
+
+/// Maximum number of tags on a bounty
+pub const MAX_TAGS: usize = 10;
+
+/// Maximum length of a single tag, in bytes
+pub const MAX_TAG_LENGTH: usize = 32;
+
+/// Maximum number of per-bounty hooks, bounding the messages sent on each event
+pub const MAX_HOOKS: usize = 5;
+
+/// Maximum number of entries in a contest, bounding the work of settling it
+pub const MAX_CONTEST_ENTRIES: u64 = 100;
+
+/// Default time the issuer has to veto an oracle attestation, in seconds
+pub const DEFAULT_VETO_PERIOD: u64 = 86_400;
+
+/// Validate the parameters of a new bounty against the configured bounds.
+///
+/// `quantity` may only be zero when the bounty pays out `nft_rewards` instead.
+pub fn validate_create_bounty(
+    config: &Config,
+    env: &Env,
+    msg: &CreateBountyMsg,
+    nft_rewards: &[NftReward],
+) -> Result<(), ContractError> {
+    if msg.title.trim().is_empty() {
+        return Err(ContractError::EmptyTitle {});
+    }
+    if msg.title.len() > config.max_title_length as usize {
+        return Err(ContractError::TitleTooLong {
+            max: config.max_title_length,
+        });
+    }
+    if msg.description.len() > config.max_description_length as usize {
+        return Err(ContractError::DescriptionTooLong {
+            max: config.max_description_length,
+        });
+    }
+
+    validate_deadline(env, msg.end_height, msg.end_time)?;
+
+    if msg.quantity.is_zero() && nft_rewards.is_empty() {
+        return Err(ContractError::ZeroQuantity {});
+    }
+
+    if msg.tags.len() > MAX_TAGS
+        || msg
+            .tags
+            .iter()
+            .any(|tag| tag.is_empty() || tag.len() > MAX_TAG_LENGTH)
+    {
+        return Err(ContractError::InvalidTags {
+            max_tags: MAX_TAGS,
+            max_length: MAX_TAG_LENGTH,
+        });
+    }
+
+    if msg.hooks.len() > MAX_HOOKS {
+        return Err(ContractError::TooManyHooks { max: MAX_HOOKS });
+    }
+
+    validate_reviewers(msg)?;
+
+    if let Some(contest) = &msg.contest {
+        validate_contest(env, msg, contest)?;
+    }
+
+    if let Some(tiers) = &msg.severity_tiers {
+        validate_severity_tiers(msg, tiers)?;
+    }
+
+    if let Some(bond) = msg.hunter_bond {
+        if bond.is_zero()
+            || msg.recipient.is_some()
+            || msg.contest.is_some()
+            || msg.severity_tiers.is_some()
+        {
+            return Err(ContractError::InvalidHunterBond {});
+        }
+    }
+
+    if let Some(usd_value) = msg.usd_value {
+        if usd_value.is_zero() || msg.contest.is_some() || msg.severity_tiers.is_some() {
+            return Err(ContractError::InvalidUsdValue {});
+        }
+        if config.price_oracle.is_none() {
+            return Err(ContractError::PriceOracleNotSet {});
+        }
+    }
+
+    if let Some(period) = &msg.assignment_period {
+        let blocks = period.blocks.unwrap_or_default();
+        let seconds = period.seconds.unwrap_or_default();
+        if blocks == 0 && seconds == 0 {
+            return Err(ContractError::InvalidAssignmentPeriod {});
+        }
+    }
+
+    if let Some(recurrence) = &msg.recurrence {
+        if recurrence.max_cycles == 0 || msg.quantity.is_zero() || msg.contest.is_some() {
+            return Err(ContractError::InvalidRecurrence {});
+        }
+    }
+
+    if let Some(key) = &msg.approver_key {
+        validate_approver_key(key)?;
+    }
+
+    if let Some(terms) = &msg.vesting {
+        validate_vesting_terms(terms)?;
+    }
+
+    Ok(())
+}
+
+/// Reviewers must be distinct and able to reach the threshold, which is unused without them.
+///
+/// Reviewed bounties can't be finalized by an approver key or oracle, which would skip the vote.
+fn validate_reviewers(msg: &CreateBountyMsg) -> Result<(), ContractError> {
+    let (reviewers, threshold) = (&msg.reviewers, msg.threshold);
+    if !reviewers.is_empty() && (msg.approver_key.is_some() || msg.oracle.is_some()) {
+        return Err(ContractError::ReviewersBypassed {});
+    }
+    let distinct = reviewers
+        .iter()
+        .enumerate()
+        .all(|(i, reviewer)| !reviewers[..i].contains(reviewer));
+    let valid = if reviewers.is_empty() {
+        threshold == 0
+    } else {
+        distinct && threshold >= 1 && threshold as usize <= reviewers.len()
+    };
+    if !valid {
+        return Err(ContractError::InvalidThreshold {});
+    }
+    Ok(())
+}
+
+/// Voting must end after submissions close and the prizes must add up to the quantity.
+///
+/// Contests pay out on their own, so they cannot be combined with held or vested payouts.
+fn validate_contest(
+    env: &Env,
+    msg: &CreateBountyMsg,
+    contest: &ContestMsg,
+) -> Result<(), ContractError> {
+    let commit_reveal = contest.reveal_end_height.is_some() || contest.reveal_end_time.is_some();
+    let reveal = !commit_reveal
+        || ends_after(
+            env,
+            (contest.reveal_end_height, contest.reveal_end_time),
+            (msg.end_height, msg.end_time),
+        );
+    let voting = ends_after(
+        env,
+        (contest.voting_end_height, contest.voting_end_time),
+        (
+            contest.reveal_end_height.or(msg.end_height),
+            contest.reveal_end_time.or(msg.end_time),
+        ),
+    );
+    let voters = match &contest.voters {
+        VotersMsg::Whitelist(addresses) => !addresses.is_empty(),
+        VotersMsg::Staking(_) => true,
+    };
+    let prizes = contest
+        .prizes
+        .iter()
+        .try_fold(Uint128::zero(), |sum, prize| sum.checked_add(*prize))
+        .map_err(|_| ContractError::InvalidContest {})?;
+
+    let valid = reveal
+        && voting
+        && voters
+        && !contest.prizes.is_empty()
+        && prizes == msg.quantity
+        && msg.release_delay.is_none()
+        && msg.vesting.is_none()
+        && msg.reviewers.is_empty();
+    if !valid {
+        return Err(ContractError::InvalidContest {});
+    }
+    Ok(())
+}
+
+/// Tiers must pay something, fit in the pool, and stick to per-report payouts
+fn validate_severity_tiers(
+    msg: &CreateBountyMsg,
+    tiers: &SeverityTiers,
+) -> Result<(), ContractError> {
+    let amounts = [tiers.low, tiers.medium, tiers.high, tiers.critical];
+    let valid = amounts.iter().any(|amount| !amount.is_zero())
+        && amounts.iter().all(|amount| *amount <= msg.quantity)
+        && msg.contest.is_none()
+        && msg.recurrence.is_none()
+        && msg.vesting.is_none()
+        && msg.release_delay.is_none();
+    if !valid {
+        return Err(ContractError::InvalidSeverityTiers {});
+    }
+    Ok(())
+}
+
+/// Whether a period `end` is set and falls after the previous period's `start`, or after the
+/// current block where the previous period has no bound of the same kind
+fn ends_after(
+    env: &Env,
+    end: (Option<u64>, Option<Timestamp>),
+    start: (Option<u64>, Option<Timestamp>),
+) -> bool {
+    let height = end
+        .0
+        .map(|height| height > start.0.unwrap_or(env.block.height));
+    let time = end.1.map(|time| time > start.1.unwrap_or(env.block.time));
+    (height.is_some() || time.is_some()) && height != Some(false) && time != Some(false)
+}
+
+/// Vesting must take some time and the cliff must fall within it
+fn validate_vesting_terms(terms: &VestingTerms) -> Result<(), ContractError> {
+    let valid = match *terms {
+        VestingTerms::Linear { cliff, duration } => duration > 0 && cliff <= duration,
+        VestingTerms::Cliff { duration } => duration > 0,
+    };
+    if !valid {
+        return Err(ContractError::InvalidVestingTerms {});
+    }
+    Ok(())
+}
+
+/// Check the public key length matches its signature scheme
+fn validate_approver_key(key: &ApproverKey) -> Result<(), ContractError> {
+    let valid = match key.scheme {
+        // compressed or uncompressed SEC1 encoding
+        SignatureScheme::Secp256k1 => matches!(key.pubkey.len(), 33 | 65),
+        SignatureScheme::Ed25519 => key.pubkey.len() == 32,
+    };
+    if !valid {
+        return Err(ContractError::InvalidPubkey {});
+    }
+    Ok(())
+}
+
+/// Ensure at least one deadline is set and none of them has already passed
+fn validate_deadline(
+    env: &Env,
+    end_height: Option<u64>,
+    end_time: Option<Timestamp>,
+) -> Result<(), ContractError> {
+    if end_height.is_none() && end_time.is_none() {
+        return Err(ContractError::MissingDeadline {});
+    }
+    if let Some(end_height) = end_height {
+        if end_height <= env.block.height {
+            return Err(ContractError::DeadlineInPast {});
+        }
+    }
+    if let Some(end_time) = end_time {
+        if end_time <= env.block.time {
+            return Err(ContractError::DeadlineInPast {});
+        }
+    }
+    Ok(())
+}
+
+/// Check an IBC payout destination is complete
+pub fn validate_ibc_destination(destination: &IbcDestination) -> Result<(), ContractError> {
+    if destination.channel_id.is_empty()
+        || destination.receiver.is_empty()
+        || destination.timeout == 0
+    {
+        return Err(ContractError::InvalidIbcDestination {});
+    }
+    Ok(())
+}
+
+/// Validate the funds sent with a new bounty, returning the amount sent above `quantity`
+pub fn validate_funds(
+    funds: &[Coin],
+    denom: &str,
+    quantity: Uint128,
+) -> Result<Uint128, ContractError> {
+    if funds.is_empty() && quantity.is_zero() {
+        return Ok(Uint128::zero());
+    }
+    let [coin] = funds else {
+        return Err(ContractError::InvalidFunds {});
+    };
+    if coin.denom != denom {
+        return Err(ContractError::InvalidFunds {});
+    }
+    if coin.amount < quantity {
+        return Err(ContractError::InsufficientFunds {});
+    }
+    Ok(coin.amount - quantity)
+}